@@ -1,17 +1,28 @@
 use aws_sign_v4;
+use std::future::Future;
 use url::Url;
 
 const CONTENT_HASH: &str = "X-Amz-Content-Sha256";
 const X_DATE: &str = "X-Amz-Date";
+const SECURITY_TOKEN: &str = "X-Amz-Security-Token";
 const EMPTY_HASH: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
 
 // #[tokio::main]
-pub async fn get_request(
+// client 由调用方构建，以便使用与 S3 客户端相同的 TLS 配置；
+// 签名后的请求交给 send 发送，这个文件同时是 admin 程序的入口，不能依赖 crate 内的其它模块
+pub async fn get_request<F, Fut>(
+    client: &reqwest::Client,
     ak: String,
     sk: String,
+    session_token: Option<String>,
     url: String,
     region: String,
-) -> Result<String, Box<dyn std::error::Error>> {
+    send: F,
+) -> Result<String, Box<dyn std::error::Error>>
+where
+    F: FnOnce(reqwest::Request) -> Fut,
+    Fut: Future<Output = reqwest::Result<reqwest::Response>>,
+{
     // let data = "";
     // // 创建一个Sha256对象
     // let mut hasher = Sha256::new();
@@ -28,7 +39,7 @@ pub async fn get_request(
     //let url = "http://127.0.0.1:9000/minio/admin/v3/info?metrics=false";
 
     let purl = Url::parse(&url)?;
-    println!("host is:{}", purl.host_str().unwrap());
+    tracing::debug!("host is:{}", purl.host_str().unwrap());
 
     let mut headers = reqwest::header::HeaderMap::new();
 
@@ -49,21 +60,23 @@ pub async fn get_request(
     );
 
     headers.insert(CONTENT_HASH, EMPTY_HASH.to_string().parse().unwrap());
+    // 临时凭证的 token 需要参与签名
+    if let Some(token) = session_token.filter(|token| !token.is_empty()) {
+        headers.insert(SECURITY_TOKEN, token.parse()?);
+    }
 
     let s = aws_sign_v4::AwsSign::new(
         "GET", &url, &datetime, &headers, &region, &ak, &sk, "s3", "",
     );
     let signature = s.sign();
-    println!("{:#?}", signature);
     headers.insert(reqwest::header::AUTHORIZATION, signature.parse().unwrap());
 
-    let client = reqwest::Client::new();
-    let res = client
+    let request = client
         .get(url)
         .headers(headers.to_owned())
         .body("")
-        .send()
-        .await?;
+        .build()?;
+    let res = send(request).await?;
 
     //println!("Status: {}", res.status());
     let body = res.text().await?;
@@ -73,11 +86,15 @@ pub async fn get_request(
 
 #[tokio::main]
 async fn main() {
+    let client = reqwest::Client::new();
     let res = get_request(
+        &client,
         "12345678".to_string(),
         "12345678".to_string(),
+        None,
         "http://127.0.0.1:9000/minio/admin/v3/info?metrics=false".to_string(),
         "us-east-1".to_string(),
+        |request| client.execute(request),
     )
     .await;
     println!("\n");
@@ -88,3 +105,39 @@ async fn main() {
         Err(e) => eprintln!("Error: {}", e),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 签名后的请求交给调用方的 send 发送，rustfs-cli 借此经过 httptrace 记录 --debug 日志
+    #[test]
+    fn test_get_request_uses_send_hook() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let mut sent = None;
+        let body = runtime
+            .block_on(get_request(
+                &reqwest::Client::new(),
+                "ak".to_string(),
+                "sk".to_string(),
+                Some("token".to_string()),
+                "http://127.0.0.1:9000/minio/admin/v3/info".to_string(),
+                "us-east-1".to_string(),
+                |request| {
+                    sent = Some(request);
+                    async { Ok(reqwest::Response::from(http::Response::new("{}"))) }
+                },
+            ))
+            .unwrap();
+
+        assert_eq!(body, "{}");
+        let request = sent.unwrap();
+        assert_eq!(request.url().path(), "/minio/admin/v3/info");
+        assert_eq!(request.headers()[SECURITY_TOKEN], "token");
+        assert!(request
+            .headers()
+            .contains_key(reqwest::header::AUTHORIZATION));
+    }
+}
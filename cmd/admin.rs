@@ -1,5 +1,8 @@
 use clap::Subcommand;
+use serde::Serialize;
 
+use super::errors::CliError;
+use super::printer::{self, Message};
 use crate::infocommands;
 
 #[derive(Subcommand)]
@@ -77,62 +80,45 @@ pub enum AdminCommands {
     Logs,
 }
 
-pub async fn handle_admin_commands(command: &AdminCommands) {
-    match command {
-        AdminCommands::Service => {
-            println!("Restarting or unfreezing the MinIO cluster...");
-        }
-        AdminCommands::Update => {
-            println!("Updating all MinIO servers...");
-        }
-        AdminCommands::Info => {
-            infocommands::ServerInfo().await;
-            println!("Displaying MinIO server information...");
-        }
-        AdminCommands::User => {
-            println!("Managing users...");
-        }
-        AdminCommands::Group => {
-            println!("Managing groups...");
-        }
-        AdminCommands::Policy => {
-            println!("Managing policies defined in the MinIO server...");
-        }
-        AdminCommands::Replicate => {
-            println!("Managing MinIO site replication...");
-        }
-        AdminCommands::Config => {
-            println!("Managing MinIO server configuration...");
-        }
-        AdminCommands::Decommission => {
-            println!("Managing MinIO server pool decommissioning...");
-        }
-        AdminCommands::Heal => {
-            println!("Monitoring healing for buckets and objects on MinIO server...");
-        }
-        AdminCommands::Prometheus => {
-            println!("Managing Prometheus config...");
-        }
-        AdminCommands::Kms => {
-            println!("Performing KMS management operations...");
-        }
-        AdminCommands::Scanner => {
-            println!("Providing MinIO scanner info...");
-        }
-        AdminCommands::Top => {
-            println!("Providing top-like statistics for MinIO...");
-        }
-        AdminCommands::Trace => {
-            println!("Showing HTTP call trace for all incoming and internode on MinIO...");
-        }
-        AdminCommands::Cluster => {
-            println!("Managing MinIO cluster metadata...");
-        }
-        AdminCommands::Rebalance => {
-            println!("Managing MinIO rebalance...");
-        }
-        AdminCommands::Logs => {
-            println!("Showing MinIO logs...");
-        }
+// 尚未实现的 admin 子命令输出的提示
+#[derive(Serialize)]
+struct AdminMessage {
+    status: &'static str,
+    message: &'static str,
+}
+
+impl Message for AdminMessage {
+    fn to_text(&self) -> String {
+        self.message.to_string()
     }
 }
+
+pub async fn handle_admin_commands(command: &AdminCommands) -> Result<(), CliError> {
+    let message = match command {
+        AdminCommands::Service => "Restarting or unfreezing the MinIO cluster...",
+        AdminCommands::Update => "Updating all MinIO servers...",
+        AdminCommands::Info => return infocommands::ServerInfo().await,
+        AdminCommands::User => "Managing users...",
+        AdminCommands::Group => "Managing groups...",
+        AdminCommands::Policy => "Managing policies defined in the MinIO server...",
+        AdminCommands::Replicate => "Managing MinIO site replication...",
+        AdminCommands::Config => "Managing MinIO server configuration...",
+        AdminCommands::Decommission => "Managing MinIO server pool decommissioning...",
+        AdminCommands::Heal => "Monitoring healing for buckets and objects on MinIO server...",
+        AdminCommands::Prometheus => "Managing Prometheus config...",
+        AdminCommands::Kms => "Performing KMS management operations...",
+        AdminCommands::Scanner => "Providing MinIO scanner info...",
+        AdminCommands::Top => "Providing top-like statistics for MinIO...",
+        AdminCommands::Trace => {
+            "Showing HTTP call trace for all incoming and internode on MinIO..."
+        }
+        AdminCommands::Cluster => "Managing MinIO cluster metadata...",
+        AdminCommands::Rebalance => "Managing MinIO rebalance...",
+        AdminCommands::Logs => "Showing MinIO logs...",
+    };
+    printer::print_msg(&AdminMessage {
+        status: "success",
+        message,
+    });
+    Ok(())
+}
@@ -6,6 +6,8 @@ use std::error::Error;
 use std::fmt;
 use std::time::{Duration, SystemTime};
 
+use super::printer::{self, Message};
+
 #[derive(Debug, Serialize, Deserialize)]
 struct ClusterInfo {
     servers: Vec<ServerInfo>,
@@ -57,6 +59,12 @@ impl ClusterStruct {
     }
 }
 
+impl Message for ClusterStruct {
+    fn to_text(&self) -> String {
+        self.to_string()
+    }
+}
+
 impl fmt::Display for ClusterStruct {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.status == "error" {
@@ -166,7 +174,7 @@ fn run(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
     });
 
     // Print cluster information
-    println!("{}", cluster_info);
+    printer::print_msg(&cluster_info);
 
     Ok(())
 }
@@ -1,6 +1,12 @@
 use clap::Subcommand;
+use serde::Serialize;
 
-use super::{aliasexport, aliaslist, aliasremove, aliasset};
+use super::errors::CliError;
+use super::printer::{self, Message};
+use super::{
+    aliasedit, aliasexport, aliasimport, aliaslist, aliasremove, aliasrename, aliasset, globals,
+    secretstore,
+};
 
 #[derive(Debug, Clone)]
 pub struct AliasMessage {
@@ -11,6 +17,86 @@ pub struct AliasMessage {
     pub api: String,
     pub path: String,
 }
+// alias 子命令执行的操作，JSON 输出中为小写的操作名
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum AliasOp {
+    Set,
+    Import,
+    Skip,
+    Merge,
+    Overwrite,
+    Remove,
+    Rename,
+    Copy,
+    Edit,
+}
+
+impl From<aliasimport::ImportAction> for AliasOp {
+    fn from(action: aliasimport::ImportAction) -> Self {
+        match action {
+            aliasimport::ImportAction::Added => AliasOp::Import,
+            aliasimport::ImportAction::Skipped => AliasOp::Skip,
+            aliasimport::ImportAction::Merged => AliasOp::Merge,
+            aliasimport::ImportAction::Overwritten => AliasOp::Overwrite,
+        }
+    }
+}
+
+// alias 子命令成功时输出的消息
+#[derive(Serialize)]
+struct AliasOpMessage<'a> {
+    status: &'static str,
+    op: AliasOp,
+    alias: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target: Option<&'a str>,
+}
+
+impl Message for AliasOpMessage<'_> {
+    fn to_text(&self) -> String {
+        let target = self.target.unwrap_or_default();
+        match self.op {
+            AliasOp::Set => format!("Added `{}` successfully.", self.alias),
+            AliasOp::Import => format!("Imported `{}` successfully.", self.alias),
+            AliasOp::Skip => format!("Skipped `{}`: alias already exists.", self.alias),
+            AliasOp::Merge => format!("Merged `{}` successfully.", self.alias),
+            AliasOp::Overwrite => format!("Overwrote `{}` successfully.", self.alias),
+            AliasOp::Remove => format!("Alias '{}' removed successfully!", self.alias),
+            AliasOp::Rename => format!(
+                "Alias '{}' renamed to '{}' successfully!",
+                self.alias, target
+            ),
+            AliasOp::Copy => format!(
+                "Alias '{}' copied to '{}' successfully!",
+                self.alias, target
+            ),
+            AliasOp::Edit => format!("Alias '{}' updated successfully!", self.alias),
+        }
+    }
+}
+
+fn print_alias_op(op: AliasOp, alias: &str, target: Option<&str>) {
+    printer::print_msg(&AliasOpMessage {
+        status: "success",
+        op,
+        alias,
+        target,
+    });
+}
+
+#[derive(Serialize)]
+struct EncryptMessage {
+    status: String,
+    count: usize,
+}
+
+impl Message for EncryptMessage {
+    fn to_text(&self) -> String {
+        format!("Encrypted credentials of {} alias(es).", self.count)
+    }
+}
+
 #[derive(Subcommand)]
 pub enum AliasCommands {
     #[command(about = "set a new alias to configuration file")]
@@ -18,19 +104,12 @@ pub enum AliasCommands {
     List {
         #[arg(help = "section")]
         alias_name: Option<String>,
+        #[arg(long, help = "show secret keys and session tokens in cleartext")]
+        show_secrets: bool,
     },
     #[clap(visible_alias = "s")]
     #[command(about = "list aliases in configuration file")]
-    Set {
-        // 定义 `alias` 名称
-        alias: String,
-        // 定义 `URL` 参数
-        url: String,
-        // 定义 `Access Key`
-        access_key: String,
-        // 定义 `Secret Key`
-        secret_key: String,
-    },
+    Set(aliasset::SetOptions),
     #[clap(visible_alias = "rm")]
     #[command(about = "remove an alias from configuration file")]
     Remove { alias: String },
@@ -38,56 +117,171 @@ pub enum AliasCommands {
     #[command(
         about = "import configuration info to configuration file from a JSON formatted string"
     )]
-    Import,
+    Import {
+        #[arg(required_unless_present_any = ["aws_profile", "all_profiles", "all", "from_mc"])]
+        alias: Option<String>,
+        #[arg(
+            long,
+            conflicts_with_all = ["aws_profile", "all_profiles", "from_mc"],
+            help = "read the JSON from a file instead of stdin"
+        )]
+        file: Option<String>,
+        #[arg(
+            long,
+            help = "import a profile from ~/.aws/credentials and ~/.aws/config, named after the profile unless an alias is given"
+        )]
+        aws_profile: Option<String>,
+        #[arg(
+            long,
+            conflicts_with_all = ["alias", "aws_profile"],
+            help = "import every profile from ~/.aws/credentials and ~/.aws/config"
+        )]
+        all_profiles: bool,
+        #[arg(
+            long,
+            conflicts_with_all = ["alias", "aws_profile", "all_profiles"],
+            help = "import every alias from the output of `alias export --all`"
+        )]
+        all: bool,
+        #[arg(
+            long,
+            value_name = "CONFIG",
+            conflicts_with_all = ["alias", "aws_profile", "all_profiles", "all"],
+            help = "import every alias from an mc config file, e.g. ~/.mc/config.json"
+        )]
+        from_mc: Option<String>,
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = aliasimport::OnConflict::Skip,
+            help = "how to handle aliases that already exist"
+        )]
+        on_conflict: aliasimport::OnConflict,
+    },
     #[clap(visible_alias = "e")]
     #[command(about = "export configuration info to stdout")]
-    Export { alias: String }, //     Get
+    Export {
+        #[arg(required_unless_present = "all")]
+        alias: Option<String>,
+        #[arg(long, conflicts_with = "alias", help = "export every alias")]
+        all: bool,
+        #[arg(
+            long,
+            help = "mask keys and tokens so the output can be shared, otherwise encrypted credentials are exported as ciphertext"
+        )]
+        redact: bool,
+    }, //     Get
+    #[command(about = "encrypt the credentials of all aliases in configuration file")]
+    EncryptAll,
+    #[clap(visible_alias = "mv")]
+    #[command(about = "rename an alias in configuration file")]
+    Rename {
+        old: String,
+        new: String,
+        #[arg(long, help = "overwrite the target alias if it exists")]
+        force: bool,
+    },
+    #[clap(visible_alias = "cp")]
+    #[command(about = "copy an alias to a new name in configuration file")]
+    Copy {
+        src: String,
+        dst: String,
+        #[arg(long, help = "overwrite the target alias if it exists")]
+        force: bool,
+    },
+    #[command(about = "change some fields of an alias without re-entering the others")]
+    Edit(aliasedit::EditOptions),
 }
 
 // 处理 run 命令的逻辑
-pub async fn handle_alias_commands(subcommand: &AliasCommands) {
+pub async fn handle_alias_commands(subcommand: &AliasCommands) -> Result<(), CliError> {
     match subcommand {
-        AliasCommands::List { alias_name } => {
-            if let Some(ref name) = alias_name {
-                println!("Listing alias: {}", name);
-                aliaslist::alias_list(name);
-            } else {
-                aliaslist::alias_list("");
+        AliasCommands::List {
+            alias_name,
+            show_secrets,
+        } => aliaslist::alias_list(
+            alias_name.as_deref().unwrap_or(""),
+            *show_secrets,
+            globals::global_flags().json,
+        )?,
+        AliasCommands::Set(opts) => {
+            let msg = aliasset::main_set_alias(opts).await.map_err(|e| {
+                CliError::from(e).context(format!("Failed to set alias `{}`", opts.alias))
+            })?;
+            print_alias_op(AliasOp::Set, &msg.alias, None);
+        }
+        AliasCommands::Export { alias, all, redact } => {
+            let export_err = |e| CliError::from(e).context("Failed to export aliases");
+            match alias {
+                Some(alias) if !*all => printer::print_json(
+                    &aliasexport::export_alias(alias, *redact).map_err(export_err)?,
+                ),
+                _ => printer::print_json(
+                    &aliasexport::export_all_aliases(*redact).map_err(export_err)?,
+                ),
             }
         }
-        AliasCommands::Set {
+        AliasCommands::Import {
             alias,
-            url,
-            access_key,
-            secret_key,
+            file,
+            aws_profile,
+            all_profiles,
+            all,
+            from_mc,
+            on_conflict,
         } => {
-            let _ = aliasset::main_set_alias(&alias, &url, &access_key, &secret_key).await;
-        }
-        AliasCommands::Export { alias } => {
-            match aliasexport::export_alias(alias) {
-                Ok(json_string) => {
-                    // Successfully serialized the alias content
-                    println!("{}", json_string);
-                }
-                Err(e) => {
-                    // Handle the error, like alias not found or config loading error
-                    eprintln!("Error: {}", e);
-                }
+            let on_conflict = *on_conflict;
+            let actions = if *all_profiles {
+                aliasimport::import_all_aws_profiles(on_conflict)
+            } else if let Some(path) = from_mc {
+                aliasimport::import_mc_config(path, on_conflict)
+            } else if *all {
+                aliasimport::import_all_aliases(file.as_deref(), on_conflict)
+            } else if let Some(profile) = aws_profile {
+                aliasimport::import_aws_profile(alias.as_deref(), profile, on_conflict)
+                    .map(|action| vec![action])
+            } else {
+                let alias = alias.as_deref().unwrap_or_default();
+                aliasimport::import_alias(alias, file.as_deref(), on_conflict)
+                    .map(|action| vec![(alias.to_string(), action)])
+            }
+            .map_err(|e| CliError::from(e).context("Failed to import aliases"))?;
+            for (alias, action) in actions {
+                print_alias_op(action.into(), &alias, None);
             }
-        }
-        &AliasCommands::Import => {
-            println!("Stopping...");
         }
         AliasCommands::Remove { alias } => {
-            let ret = aliasremove::remove_alias(&alias);
-            match ret {
-                Ok(_) => {
-                    println!("Alias '{}' removed successfully!", alias);
-                }
-                Err(e) => {
-                    println!("Failed to remove alias '{}'. Error: {:?}", alias, e);
-                }
-            }
+            aliasremove::remove_alias(alias).map_err(|e| {
+                CliError::from(e).context(format!("Failed to remove alias `{}`", alias))
+            })?;
+            print_alias_op(AliasOp::Remove, alias, None);
+        }
+        AliasCommands::Rename { old, new, force } => {
+            aliasrename::rename_alias(old, new, *force).map_err(|e| {
+                CliError::from(e).context(format!("Failed to rename alias `{}`", old))
+            })?;
+            print_alias_op(AliasOp::Rename, old, Some(new));
+        }
+        AliasCommands::Copy { src, dst, force } => {
+            aliasrename::copy_alias(src, dst, *force).map_err(|e| {
+                CliError::from(e).context(format!("Failed to copy alias `{}`", src))
+            })?;
+            print_alias_op(AliasOp::Copy, src, Some(dst));
+        }
+        AliasCommands::Edit(opts) => {
+            aliasedit::edit_alias(opts).map_err(|e| {
+                CliError::from(e).context(format!("Failed to edit alias `{}`", opts.alias))
+            })?;
+            print_alias_op(AliasOp::Edit, &opts.alias, None);
+        }
+        AliasCommands::EncryptAll => {
+            let count = secretstore::encrypt_all_aliases()
+                .map_err(|e| CliError::from(e).context("Failed to encrypt aliases"))?;
+            printer::print_msg(&EncryptMessage {
+                status: "success".to_string(),
+                count,
+            });
         }
     }
+    Ok(())
 }
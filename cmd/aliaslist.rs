@@ -1,9 +1,13 @@
 // use clap::{Arg, Command};
+use prettytable::{format, Cell, Row, Table};
+use serde::Serialize;
 use std::collections::HashMap;
-use std::fmt;
 
 use super::config;
 use super::configx::AliasConfigV10;
+use super::errors::{self, CliError};
+use super::printer::{self, Message};
+use super::secretstore;
 
 // 定义 alias 配置结构
 //#[derive(Debug, Clone)]
@@ -15,64 +19,149 @@ use super::configx::AliasConfigV10;
 //     path: Option<String>,
 // }
 
-// 定义 alias 信息结构
-#[derive(Debug, Clone)]
+// 隐藏的凭证显示为该值，不暴露长度
+const MASKED_SECRET: &str = "********";
+
+// 定义 alias 信息结构，JSON 字段名与 mc 保持一致
+#[derive(Debug, Clone, Serialize)]
 struct AliasMessage {
+    status: &'static str,
     alias: String,
+    #[serde(rename = "URL")]
     url: String,
+    #[serde(rename = "accessKey")]
     access_key: String,
+    #[serde(rename = "secretKey")]
     secret_key: String,
+    #[serde(rename = "sessionToken", skip_serializing_if = "Option::is_none")]
+    session_token: Option<String>,
     api: String,
     path: String,
 }
 
-impl fmt::Display for AliasMessage {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "{}\n\tURL: {}\n\tAccessKey: {}\n\tSecretKey: {}\n\tAPI: {}\n\tPath: {}\n",
+// 与 mc 的文本输出相同，列表默认以表格输出
+impl Message for AliasMessage {
+    fn to_text(&self) -> String {
+        format!(
+            "{}\n  URL       : {}\n  AccessKey : {}\n  SecretKey : {}\n  API       : {}\n  Path      : {}",
             self.alias, self.url, self.access_key, self.secret_key, self.api, self.path
         )
     }
 }
 
-pub fn alias_list(name: &str) {
-    config::alias_list(name);
+pub fn alias_list(name: &str, show_secrets: bool, json: bool) -> Result<(), CliError> {
+    config::alias_list(name, show_secrets, json)
 }
 
-// 列出所有 aliases
-pub fn list_aliases(aliases: &HashMap<String, AliasConfigV10>, alias_name: Option<&str>) {
-    if let Some(alias) = alias_name {
-        if let Some(config) = aliases.get(alias) {
-            let msg = build_alias_message(alias, config);
-            println!("{}", msg);
-        } else {
-            println!("No such alias `{}` found.", alias);
+// 列出所有 aliases，默认以表格输出，json 为 true 时每个别名输出一行 JSON
+pub fn list_aliases(
+    aliases: &HashMap<String, AliasConfigV10>,
+    alias_name: Option<&str>,
+    show_secrets: bool,
+    json: bool,
+) -> Result<(), CliError> {
+    let mut alias_list: Vec<_> = match alias_name {
+        Some(alias) if !aliases.contains_key(alias) => {
+            return Err(errors::alias_not_found(alias, aliases.keys()));
         }
-    } else {
-        let mut alias_list: Vec<_> = aliases.keys().collect();
-        alias_list.sort();
-        for alias in alias_list {
-            let config = &aliases[alias];
-            let msg = build_alias_message(alias, config);
-            println!("{}", msg);
+        Some(alias) => vec![alias],
+        None => aliases.keys().map(String::as_str).collect(),
+    };
+    alias_list.sort();
+
+    let mut messages = Vec::new();
+    for alias in alias_list {
+        // 与 get_alias 相同，加密保存的凭证只在需要显示明文时解密
+        let mut alias_cfg = aliases[alias].clone();
+        if show_secrets {
+            secretstore::decrypt_alias(&mut alias_cfg).map_err(|e| {
+                CliError::from(e).context(format!("Unable to decrypt credentials of `{}`", alias))
+            })?;
+        }
+        messages.push(build_alias_message(alias, &alias_cfg, show_secrets));
+    }
+    if json {
+        for msg in &messages {
+            printer::print_msg(msg);
         }
+    } else {
+        print_alias_table(&messages);
+    }
+    Ok(())
+}
+
+fn print_alias_table(messages: &[AliasMessage]) {
+    let mut table = Table::new();
+    table.set_format(*format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    table.set_titles(Row::new(
+        ["Alias", "URL", "AccessKey", "SecretKey", "API", "Path"]
+            .iter()
+            .map(|title| Cell::new(title))
+            .collect(),
+    ));
+    for msg in messages {
+        table.add_row(Row::new(vec![
+            Cell::new(&msg.alias),
+            Cell::new(&msg.url),
+            Cell::new(&msg.access_key),
+            Cell::new(&msg.secret_key),
+            Cell::new(&msg.api),
+            Cell::new(&msg.path),
+        ]));
+    }
+    table.printstd();
+}
+
+// 隐藏凭证，未设置的凭证保持为空
+fn mask_secret(secret: &str, show_secrets: bool) -> String {
+    if show_secrets || secret.is_empty() {
+        secret.to_string()
+    } else {
+        MASKED_SECRET.to_string()
     }
 }
 
 // 构建 alias 消息
-fn build_alias_message(alias: &str, config: &AliasConfigV10) -> AliasMessage {
-    let access_key = &config.access_key;
+fn build_alias_message(alias: &str, config: &AliasConfigV10, show_secrets: bool) -> AliasMessage {
     AliasMessage {
+        status: "success",
         alias: alias.to_string(),
         url: config.url.clone(),
-        access_key: access_key.clone(),
-        secret_key: config.secret_key.clone(),
+        access_key: config.access_key.clone(),
+        secret_key: mask_secret(&config.secret_key, show_secrets),
+        session_token: config
+            .session_token
+            .as_deref()
+            .map(|token| mask_secret(token, show_secrets)),
         api: config.api.clone(),
         path: config.path.clone(),
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_alias_message_masks_secrets() {
+        let config = AliasConfigV10 {
+            url: "http://localhost:9000".to_string(),
+            access_key: "ak".to_string(),
+            secret_key: "sk".to_string(),
+            session_token: Some("token".to_string()),
+            ..Default::default()
+        };
+
+        let msg = build_alias_message("local", &config, false);
+        assert_eq!(msg.access_key, "ak");
+        assert_eq!(msg.secret_key, MASKED_SECRET);
+        assert_eq!(msg.session_token.as_deref(), Some(MASKED_SECRET));
+
+        let msg = build_alias_message("local", &config, true);
+        assert_eq!(msg.secret_key, "sk");
+    }
+}
+
 // pub fn main() {
 //     // 定义命令行工具
 //     let matches = Command::new("minio-cli")
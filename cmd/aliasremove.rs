@@ -1,20 +1,15 @@
-use super::configx;
+use super::errors::alias_not_found;
+use super::{configx, secretstore};
 use crate::cmd::config;
 use std::error::Error;
 // use crate::cmd::alias;
 
-#[derive(Debug)]
-struct AliasNotFoundError;
-
-impl std::fmt::Display for AliasNotFoundError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Alias not found")
+pub fn get_alias(alias: &str) -> Result<configx::AliasConfigV10, Box<dyn Error>> {
+    // 环境变量中定义的别名优先于配置文件
+    if let Some(alias_cfg) = config::get_alias_from_env(alias) {
+        return Ok(alias_cfg);
     }
-}
 
-impl Error for AliasNotFoundError {}
-
-pub fn get_alias(alias: &str) -> Result<configx::AliasConfigV10, Box<dyn Error>> {
     let mc_cfg_v10 = configx::load_config_v10().map_err(|err| {
         eprintln!(
             "Unable to load config `{}`.",
@@ -23,38 +18,27 @@ pub fn get_alias(alias: &str) -> Result<configx::AliasConfigV10, Box<dyn Error>>
         err
     })?;
 
-    mc_cfg_v10
+    let mut alias_cfg = mc_cfg_v10
         .aliases
         .get(alias)
         .cloned() // 将引用转换为拥有权的值
-        .ok_or_else(|| Box::new(AliasNotFoundError) as Box<dyn Error>)
+        .ok_or_else(|| {
+            Box::new(alias_not_found(alias, mc_cfg_v10.aliases.keys())) as Box<dyn Error>
+        })?;
+
+    // 配置文件中加密保存的凭证在使用前解密
+    secretstore::decrypt_alias(&mut alias_cfg)?;
+    Ok(alias_cfg)
 }
 
 pub fn remove_alias(alias: &str) -> Result<(), Box<dyn Error>> {
-    // 加载配置
-    let mut mc_cfg_v10 = configx::load_config_v10().map_err(|err| {
-        eprintln!(
-            "Unable to load config `{}`.",
-            config::must_get_mc_config_dir()
-        );
-        err
-    })?;
-
-    if mc_cfg_v10.aliases.contains_key(alias) {
-        // 存在，则移除
-        mc_cfg_v10.aliases.remove(alias);
-
-        // 保存配置
-        configx::save_config_v10(&mc_cfg_v10).map_err(|err| {
-            eprintln!(
-                "Unable to update hosts in config version `{}`.",
-                config::must_get_mc_config_dir()
-            );
-            err
-        })?;
-        Ok(())
-    } else {
-        // 不存在，返回错误
-        Err(Box::new(AliasNotFoundError))
-    }
+    // 加锁加载配置，移除别名后保存
+    configx::update_config_v10(|mc_cfg_v10| {
+        if mc_cfg_v10.aliases.remove(alias).is_some() {
+            Ok(())
+        } else {
+            // 不存在，返回错误
+            Err(Box::new(alias_not_found(alias, mc_cfg_v10.aliases.keys())) as Box<dyn Error>)
+        }
+    })
 }
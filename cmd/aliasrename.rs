@@ -0,0 +1,45 @@
+use super::{config, configx, errors};
+use std::error::Error;
+
+// 将 src 别名复制为 dst，remove_src 为 true 时即为重命名；dst 已存在时需指定 force
+fn duplicate_alias(
+    src: &str,
+    dst: &str,
+    force: bool,
+    remove_src: bool,
+) -> Result<(), Box<dyn Error>> {
+    if !config::is_valid_alias(dst) {
+        return Err(format!("Invalid alias name `{}`", dst).into());
+    }
+    if src == dst {
+        return Err(format!("Source and target alias are both `{}`", src).into());
+    }
+
+    configx::update_config_v10(|mc_cfg_v10| {
+        let alias_cfg = mc_cfg_v10
+            .aliases
+            .get(src)
+            .cloned()
+            .ok_or_else(|| errors::alias_not_found(src, mc_cfg_v10.aliases.keys()))?;
+        if !force && mc_cfg_v10.aliases.contains_key(dst) {
+            return Err(format!(
+                "Alias `{}` already exists, use --force to overwrite it",
+                dst
+            )
+            .into());
+        }
+        if remove_src {
+            mc_cfg_v10.aliases.remove(src);
+        }
+        mc_cfg_v10.aliases.insert(dst.to_string(), alias_cfg);
+        Ok(())
+    })
+}
+
+pub fn rename_alias(old: &str, new: &str, force: bool) -> Result<(), Box<dyn Error>> {
+    duplicate_alias(old, new, force, true)
+}
+
+pub fn copy_alias(src: &str, dst: &str, force: bool) -> Result<(), Box<dyn Error>> {
+    duplicate_alias(src, dst, force, false)
+}
@@ -0,0 +1,210 @@
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use super::configx::AliasConfigV10;
+
+// 与 AWS CLI 相同的环境变量，用于覆盖默认的文件位置
+const AWS_ENV_CONFIG_FILE: &str = "AWS_CONFIG_FILE";
+const AWS_ENV_SHARED_CREDENTIALS_FILE: &str = "AWS_SHARED_CREDENTIALS_FILE";
+
+pub type AwsProfile = HashMap<String, String>;
+
+// 解析 INI 格式的内容，返回 section -> (key -> value)。
+// 值为空的键之后缩进的行为子配置，例如 `s3 =` 下的 `addressing_style = path`，
+// 保存为 `s3.addressing_style`
+fn parse_ini(data: &str) -> BTreeMap<String, AwsProfile> {
+    let mut sections: BTreeMap<String, AwsProfile> = BTreeMap::new();
+    let mut section: Option<String> = None;
+    let mut parent: Option<String> = None;
+
+    for line in data.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = trimmed.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+            sections.entry(name.clone()).or_default();
+            section = Some(name);
+            parent = None;
+            continue;
+        }
+        let (Some(section), Some((key, value))) = (&section, trimmed.split_once('=')) else {
+            continue;
+        };
+        let (key, value) = (key.trim().to_string(), value.trim().to_string());
+        let nested = line.starts_with(char::is_whitespace);
+        let key = match &parent {
+            Some(parent) if nested => format!("{}.{}", parent, key),
+            _ => {
+                parent = if value.is_empty() {
+                    Some(key.clone())
+                } else {
+                    None
+                };
+                key
+            }
+        };
+        sections.get_mut(section).unwrap().insert(key, value);
+    }
+    sections
+}
+
+fn aws_file_path(env_name: &str, file_name: &str) -> Option<PathBuf> {
+    match env::var(env_name) {
+        Ok(path) => Some(PathBuf::from(path)),
+        Err(_) => dirs::home_dir().map(|home| home.join(".aws").join(file_name)),
+    }
+}
+
+// 读取文件，不存在时返回 None
+fn read_optional(path: &Option<PathBuf>) -> io::Result<Option<String>> {
+    let Some(path) = path else {
+        return Ok(None);
+    };
+    match fs::read_to_string(path) {
+        Ok(data) => Ok(Some(data)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(io::Error::new(
+            err.kind(),
+            format!("Unable to read `{}`: {}", path.display(), err),
+        )),
+    }
+}
+
+// 合并 config 与 credentials 中的 profile，credentials 中的值优先
+fn merge_profiles(config: &str, credentials: &str) -> BTreeMap<String, AwsProfile> {
+    let mut profiles: BTreeMap<String, AwsProfile> = BTreeMap::new();
+    for (section, settings) in parse_ini(config) {
+        // config 中除 default 外的 profile 写作 [profile name]，其余 section（如 sso-session）忽略
+        let name = match section.strip_prefix("profile ") {
+            Some(name) => name.trim().to_string(),
+            None if section == "default" => section,
+            None => continue,
+        };
+        profiles.entry(name).or_default().extend(settings);
+    }
+    for (name, settings) in parse_ini(credentials) {
+        profiles.entry(name).or_default().extend(settings);
+    }
+    profiles
+}
+
+// 读取 ~/.aws/config 和 ~/.aws/credentials 中的所有 profile
+pub fn load_aws_profiles() -> io::Result<BTreeMap<String, AwsProfile>> {
+    let config_path = aws_file_path(AWS_ENV_CONFIG_FILE, "config");
+    let credentials_path = aws_file_path(AWS_ENV_SHARED_CREDENTIALS_FILE, "credentials");
+    let config = read_optional(&config_path)?;
+    let credentials = read_optional(&credentials_path)?;
+    if config.is_none() && credentials.is_none() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "No AWS config or credentials file found",
+        ));
+    }
+    Ok(merge_profiles(
+        &config.unwrap_or_default(),
+        &credentials.unwrap_or_default(),
+    ))
+}
+
+// 将 profile 转换为别名配置，未设置 endpoint_url 时使用 AWS S3 的地址
+pub fn profile_to_alias_config(name: &str, profile: &AwsProfile) -> Result<AliasConfigV10, String> {
+    let get = |key: &str| profile.get(key).filter(|v| !v.is_empty()).cloned();
+
+    let access_key = get("aws_access_key_id").unwrap_or_default();
+    let secret_key = get("aws_secret_access_key").unwrap_or_default();
+    let credential_process = get("credential_process");
+    if access_key.is_empty() && credential_process.is_none() {
+        return Err(format!(
+            "Profile `{}` has neither static credentials nor credential_process",
+            name
+        ));
+    }
+
+    let region = get("region");
+    let url = get("s3.endpoint_url")
+        .or_else(|| get("endpoint_url"))
+        .unwrap_or_else(|| match &region {
+            Some(region) => format!("https://s3.{}.amazonaws.com", region),
+            None => "https://s3.amazonaws.com".to_string(),
+        });
+    let path = match get("s3.addressing_style").as_deref() {
+        Some("path") => "on",
+        Some("virtual") => "off",
+        _ => "auto",
+    };
+
+    Ok(AliasConfigV10 {
+        url,
+        access_key,
+        secret_key,
+        session_token: get("aws_session_token"),
+        api: "S3v4".to_string(),
+        path: path.to_string(),
+        region,
+        credential_process,
+        src: Some(format!("aws-profile:{}", name)),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = "
+[default]
+region = us-west-2
+
+[profile rustfs]
+region = cn-east-1
+endpoint_url = http://127.0.0.1:9000
+s3 =
+  addressing_style = path
+
+[sso-session corp]
+sso_region = us-east-1
+";
+
+    const CREDENTIALS: &str = "
+# static keys
+[default]
+aws_access_key_id = AKDEFAULT
+aws_secret_access_key = SKDEFAULT
+
+[rustfs]
+aws_access_key_id = AKRUSTFS
+aws_secret_access_key = SKRUSTFS
+aws_session_token = TOKEN
+";
+
+    #[test]
+    fn test_merge_profiles() {
+        let profiles = merge_profiles(CONFIG, CREDENTIALS);
+
+        assert_eq!(profiles.keys().collect::<Vec<_>>(), ["default", "rustfs"]);
+        assert_eq!(profiles["rustfs"]["s3.addressing_style"], "path");
+        assert_eq!(profiles["rustfs"]["aws_session_token"], "TOKEN");
+    }
+
+    #[test]
+    fn test_profile_to_alias_config() {
+        let profiles = merge_profiles(CONFIG, CREDENTIALS);
+
+        let rustfs = profile_to_alias_config("rustfs", &profiles["rustfs"]).unwrap();
+        assert_eq!(rustfs.url, "http://127.0.0.1:9000");
+        assert_eq!(rustfs.path, "on");
+        assert_eq!(rustfs.region.as_deref(), Some("cn-east-1"));
+        assert_eq!(rustfs.session_token.as_deref(), Some("TOKEN"));
+
+        let default = profile_to_alias_config("default", &profiles["default"]).unwrap();
+        assert_eq!(default.url, "https://s3.us-west-2.amazonaws.com");
+        assert_eq!(default.access_key, "AKDEFAULT");
+
+        assert!(profile_to_alias_config("empty", &AwsProfile::new()).is_err());
+    }
+}
@@ -0,0 +1,89 @@
+use chrono::{DateTime, Duration, Utc};
+use lazy_static::lazy_static;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+// 与全局 --cert-expiry-warn-days 参数等效的环境变量
+pub const RC_ENV_CERT_EXPIRY_WARN_DAYS: &str = "RC_CERT_EXPIRY_WARN_DAYS";
+pub const DEFAULT_CERT_EXPIRY_WARN_DAYS: u64 = 7;
+
+lazy_static! {
+    // 本次运行中校验通过的服务端证书的过期时间，按 host:port 记录
+    static ref PEER_CERT_EXPIRY: Mutex<BTreeMap<String, DateTime<Utc>>> =
+        Mutex::new(BTreeMap::new());
+}
+
+// 记录服务端证书的过期时间，解析失败时忽略
+pub fn record_peer_cert(endpoint: &str, der: &[u8]) {
+    let Ok((_, cert)) = x509_parser::parse_x509_certificate(der) else {
+        return;
+    };
+    let Some(not_after) = DateTime::from_timestamp(cert.validity().not_after.timestamp(), 0) else {
+        return;
+    };
+    PEER_CERT_EXPIRY
+        .lock()
+        .unwrap()
+        .insert(endpoint.to_string(), not_after);
+}
+
+// 返回在 now + window 之前过期的证书
+fn expiring_certs(
+    certs: &BTreeMap<String, DateTime<Utc>>,
+    now: DateTime<Utc>,
+    window: Duration,
+) -> Vec<(String, DateTime<Utc>)> {
+    certs
+        .iter()
+        .filter(|(_, not_after)| **not_after - now < window)
+        .map(|(endpoint, not_after)| (endpoint.clone(), *not_after))
+        .collect()
+}
+
+// 命令结束后提示即将过期的证书，warn_days 为 0 时不提示
+pub fn warn_expiring_certs(warn_days: u64) {
+    if warn_days == 0 {
+        return;
+    }
+    let now = Utc::now();
+    let window = Duration::days(warn_days.min(i32::MAX as u64) as i64);
+    for (endpoint, not_after) in expiring_certs(&PEER_CERT_EXPIRY.lock().unwrap(), now, window) {
+        eprintln!();
+        match (not_after - now).to_std() {
+            Ok(remaining) => {
+                // 精确到分钟即可
+                let remaining = std::time::Duration::from_secs(remaining.as_secs() / 60 * 60);
+                eprintln!(
+                    "== WARN: `{}` certificate will expire in {} ({}). Renew soon to avoid outage.",
+                    endpoint,
+                    humantime::format_duration(remaining),
+                    not_after
+                );
+            }
+            Err(_) => eprintln!(
+                "== WARN: `{}` certificate expired on {}. Renew it to avoid outage.",
+                endpoint, not_after
+            ),
+        }
+        eprintln!();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expiring_certs() {
+        let now = Utc::now();
+        let certs = BTreeMap::from([
+            ("expired:443".to_string(), now - Duration::days(1)),
+            ("soon:443".to_string(), now + Duration::days(3)),
+            ("later:443".to_string(), now + Duration::days(30)),
+        ]);
+
+        let expiring = expiring_certs(&certs, now, Duration::days(7));
+        let endpoints: Vec<_> = expiring.iter().map(|(e, _)| e.as_str()).collect();
+        assert_eq!(endpoints, ["expired:443", "soon:443"]);
+    }
+}
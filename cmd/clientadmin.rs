@@ -4,10 +4,25 @@ use aws_sigv4::http_request::{sign, SignableRequest, SigningParams, SigningSetti
 use aws_sigv4::sign::v4;
 use http1;
 use reqwest::Client;
+use serde::Serialize;
 use std::time::SystemTime;
 //use http0;
 use aws_smithy_runtime_api::client::identity::Identity;
 
+use super::printer::{self, Message};
+
+#[derive(Serialize)]
+struct ResponseMessage {
+    status: u16,
+    body: String,
+}
+
+impl Message for ResponseMessage {
+    fn to_text(&self) -> String {
+        format!("Status: {}\nBody: {:?}", self.status, self.body)
+    }
+}
+
 //#[cfg(feature = "http1")]
 async fn test() -> Result<(), Box<dyn std::error::Error>> {
     // Set up information and settings for the signing
@@ -43,8 +58,10 @@ async fn test() -> Result<(), Box<dyn std::error::Error>> {
     let response = cli.execute(my_req.try_into()?).await?;
 
     // Print response for verification
-    println!("Status: {}", response.status());
-    println!("Body: {:?}", response.text().await?);
+    printer::print_msg(&ResponseMessage {
+        status: response.status().as_u16(),
+        body: response.text().await?,
+    });
     //reqwest::Client::
     Ok(())
 }
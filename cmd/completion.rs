@@ -0,0 +1,370 @@
+use clap::{Command, FromArgMatches, ValueEnum};
+use std::io::{self, Write};
+use std::iter;
+
+use super::errors::CliError;
+use super::globals::{self, GlobalFlags};
+use super::{config, configx};
+use crate::s3::client::get_s3client_from_alias;
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+#[derive(clap::Args, Debug)]
+#[command(after_help = "EXAMPLES:
+  # bash, add to ~/.bashrc
+  source <(rustfs-cli completion bash)
+  # zsh, add to ~/.zshrc after compinit
+  source <(rustfs-cli completion zsh)
+  # fish
+  rustfs-cli completion fish > ~/.config/fish/completions/rustfs-cli.fish")]
+pub struct CompletionOptions {
+    #[arg(value_enum, help = "shell to generate the completion script for")]
+    pub shell: CompletionShell,
+}
+
+// 补全脚本调用的隐藏命令：`rustfs-cli __complete -- <参数...>`，
+// 在 clap 解析之前处理，不会出现在帮助和生成的补全脚本中
+pub const COMPLETE_COMMAND: &str = "__complete";
+
+// 生成的脚本在 clap 的静态补全之前先调用隐藏的 __complete 命令，
+// 由它补全别名、桶和前缀，没有输出时再调用 STATIC_FN 即 clap 生成的补全函数
+const BASH_DYNAMIC: &str = r#"
+_rustfs_cli_dynamic() {
+    local candidates
+    mapfile -t candidates < <("${COMP_WORDS[0]}" __complete -- "${COMP_WORDS[@]:1:COMP_CWORD}" 2>/dev/null)
+    if [[ ${#candidates[@]} -gt 0 ]]; then
+        COMPREPLY=("${candidates[@]}")
+        if [[ ${#COMPREPLY[@]} -eq 1 && ${COMPREPLY[0]} == */ ]]; then
+            compopt -o nospace
+        fi
+        return 0
+    fi
+    STATIC_FN "$@"
+}
+complete -F _rustfs_cli_dynamic -o bashdefault -o default rustfs-cli
+"#;
+
+const ZSH_DYNAMIC: &str = r#"
+_rustfs_cli_dynamic() {
+    local -a candidates
+    candidates=("${(@f)$("${words[1]}" __complete -- "${(@)words[2,CURRENT]}" 2>/dev/null)}")
+    if [[ -n ${candidates[1]} ]]; then
+        compadd -Q -S '' -- "${candidates[@]}"
+        return
+    fi
+    STATIC_FN "$@"
+}
+compdef _rustfs_cli_dynamic rustfs-cli
+"#;
+
+const FISH_DYNAMIC: &str = r#"
+complete -c rustfs-cli -a '(rustfs-cli __complete -- (commandline -opc)[2..-1] (commandline -ct) 2>/dev/null)'
+"#;
+
+// 输出补全脚本
+pub fn print_completion(shell: CompletionShell, cmd: &mut Command) -> Result<(), CliError> {
+    let name = cmd.get_name().to_string();
+    let (generator, dynamic) = match shell {
+        CompletionShell::Bash => (clap_complete::Shell::Bash, BASH_DYNAMIC),
+        CompletionShell::Zsh => (clap_complete::Shell::Zsh, ZSH_DYNAMIC),
+        CompletionShell::Fish => (clap_complete::Shell::Fish, FISH_DYNAMIC),
+    };
+    let mut script = Vec::new();
+    clap_complete::generate(generator, cmd, name, &mut script);
+    let script = String::from_utf8_lossy(&script);
+    let dynamic = match static_completion_fn(&script) {
+        Some(static_fn) => dynamic.replace("STATIC_FN", static_fn),
+        None => dynamic.to_string(),
+    };
+    let mut stdout = io::stdout();
+    stdout.write_all(script.as_bytes())?;
+    stdout.write_all(dynamic.as_bytes())?;
+    Ok(())
+}
+
+// clap 生成的补全函数名随版本变化，从注册函数的那一行中取出，
+// 例如 bash 的 `complete -F _rustfs__cli ...`、zsh 的 `compdef _rustfs-cli rustfs-cli`
+fn static_completion_fn(script: &str) -> Option<&str> {
+    script.lines().find_map(|line| {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        match tokens.as_slice() {
+            ["complete", "-F", name, ..] | ["compdef", name, ..] => Some(*name),
+            _ => None,
+        }
+    })
+}
+
+// 需要动态补全的参数类型
+#[derive(Debug, PartialEq)]
+enum ArgKind {
+    // 别名
+    Alias,
+    // alias/bucket/prefix
+    Remote,
+}
+
+// 按子命令路径和参数 id 判断参数类型，其它参数交给静态补全
+fn arg_kind(path: &[String], id: &str) -> Option<ArgKind> {
+    let path: Vec<&str> = path.iter().map(String::as_str).collect();
+    match (path.as_slice(), id) {
+        (["ls" | "mb" | "rb" | "rm" | "find"], "path")
+        | (["put"], "target")
+        | (["cp"], "src" | "target") => Some(ArgKind::Remote),
+        (["alias", "list"], "alias_name")
+        | (["alias", "remove" | "export" | "edit"], "alias")
+        | (["alias", "rename"], "old")
+        | (["alias", "copy"], "src") => Some(ArgKind::Alias),
+        _ => None,
+    }
+}
+
+// 根据已输入的参数找出正在输入的是哪个子命令的第几个位置参数，
+// 返回子命令路径和该位置参数的 id
+fn current_positional(cmd: &Command, words: &[String]) -> Option<(Vec<String>, String)> {
+    let mut cmd = cmd;
+    let mut path = Vec::new();
+    let mut index = 0;
+    let mut skip_value = false;
+    let mut only_positionals = false;
+
+    for word in words {
+        if skip_value {
+            skip_value = false;
+            continue;
+        }
+        if !only_positionals && word == "--" {
+            only_positionals = true;
+            continue;
+        }
+        if !only_positionals && word.len() > 1 && word.starts_with('-') {
+            skip_value = option_needs_value(cmd, word);
+            continue;
+        }
+        if index == 0 {
+            if let Some(sub) = cmd.find_subcommand(word) {
+                path.push(sub.get_name().to_string());
+                cmd = sub;
+                continue;
+            }
+        }
+        index += 1;
+    }
+    if skip_value {
+        return None;
+    }
+
+    let arg = cmd.get_positionals().nth(index)?;
+    Some((path, arg.get_id().to_string()))
+}
+
+// 选项的值是否在下一个参数中，例如 `--rewind 1d`、`-C dir`
+fn option_needs_value(cmd: &Command, word: &str) -> bool {
+    let takes_value = |arg: &clap::Arg| arg.get_action().takes_values();
+    if let Some(long) = word.strip_prefix("--") {
+        if long.contains('=') {
+            return false;
+        }
+        return cmd
+            .get_arguments()
+            .find(|arg| {
+                arg.get_long() == Some(long)
+                    || arg.get_all_aliases().is_some_and(|a| a.contains(&long))
+            })
+            .is_some_and(takes_value);
+    }
+    // 短选项可以合并写，例如 -rC，只有最后一个可以把值放在下一个参数中
+    let shorts: Vec<char> = word[1..].chars().collect();
+    for (i, c) in shorts.iter().enumerate() {
+        let Some(arg) = cmd.get_arguments().find(|arg| arg.get_short() == Some(*c)) else {
+            return false;
+        };
+        if takes_value(arg) {
+            return i == shorts.len() - 1;
+        }
+    }
+    false
+}
+
+// 每行输出一个候选项，出错时不输出，由补全脚本退回到静态补全。
+// args 为命令名之后的所有参数，最后一个为正在输入的参数
+pub async fn handle_complete_command(mut cmd: Command, args: &[String]) {
+    cmd.build();
+    let args = args.strip_prefix(&["--".to_string()]).unwrap_or(args);
+    let (done, current) = match args.split_last() {
+        Some((current, done)) => (done, current.as_str()),
+        None => (args, ""),
+    };
+
+    // 已输入的 --config-dir、--insecure 等全局参数同样用于补全
+    let matches = cmd
+        .clone()
+        .ignore_errors(true)
+        .try_get_matches_from(iter::once(cmd.get_name()).chain(done.iter().map(String::as_str)));
+    if let Ok(matches) = matches {
+        if let Some(config_dir) = matches.get_one::<String>("config_dir") {
+            config::set_mc_config_dir(config_dir.clone());
+        }
+        if let Ok(flags) = GlobalFlags::from_arg_matches(&matches) {
+            globals::set_global_flags(flags);
+        }
+    }
+
+    let candidates = match current_positional(&cmd, done) {
+        Some((path, id)) => match arg_kind(&path, &id) {
+            Some(ArgKind::Alias) => complete_alias(current, ""),
+            Some(ArgKind::Remote) => complete_remote(current).await,
+            None => Vec::new(),
+        },
+        None => Vec::new(),
+    };
+    for candidate in candidates {
+        println!("{}", candidate);
+    }
+}
+
+fn complete_alias(prefix: &str, suffix: &str) -> Vec<String> {
+    let Ok(config) = configx::load_config_v10() else {
+        return Vec::new();
+    };
+    let mut aliases: Vec<String> = config
+        .aliases
+        .keys()
+        .filter(|alias| alias.starts_with(prefix))
+        .map(|alias| format!("{}{}", alias, suffix))
+        .collect();
+    aliases.sort();
+    aliases
+}
+
+// myalias -> myalias/，myalias/b -> myalias/bucket/，myalias/bucket/pre -> 该前缀下的一层目录和对象
+async fn complete_remote(word: &str) -> Vec<String> {
+    let Some((alias, rest)) = word.split_once('/') else {
+        return complete_alias(word, "/");
+    };
+    let Ok(client) = get_s3client_from_alias(alias) else {
+        return Vec::new();
+    };
+
+    let Some((bucket, prefix)) = rest.split_once('/') else {
+        let Ok(output) = client.list_buckets().send().await else {
+            return Vec::new();
+        };
+        return output
+            .buckets()
+            .iter()
+            .filter_map(|bucket| bucket.name())
+            .filter(|name| name.starts_with(rest))
+            .map(|name| format!("{}/{}/", alias, name))
+            .collect();
+    };
+
+    let Ok(output) = client
+        .list_objects_v2()
+        .bucket(bucket)
+        .prefix(prefix)
+        .delimiter("/")
+        .send()
+        .await
+    else {
+        return Vec::new();
+    };
+    let prefixes = output.common_prefixes().iter().filter_map(|p| p.prefix());
+    let keys = output.contents().iter().filter_map(|object| object.key());
+    prefixes
+        .chain(keys)
+        .map(|key| format!("{}/{}/{}", alias, bucket, key))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{Arg, ArgAction};
+
+    fn test_command() -> Command {
+        let mut cmd = Command::new("rustfs-cli")
+            .arg(
+                Arg::new("config_dir")
+                    .long("config-dir")
+                    .short('C')
+                    .global(true),
+            )
+            .arg(
+                Arg::new("json")
+                    .long("json")
+                    .global(true)
+                    .action(ArgAction::SetTrue),
+            )
+            .subcommand(
+                Command::new("ls")
+                    .arg(Arg::new("path"))
+                    .arg(Arg::new("rewind").long("rewind"))
+                    .arg(
+                        Arg::new("recursive")
+                            .long("recursive")
+                            .short('r')
+                            .action(ArgAction::SetTrue),
+                    ),
+            )
+            .subcommand(
+                Command::new("alias").subcommand(
+                    Command::new("remove")
+                        .visible_alias("rm")
+                        .arg(Arg::new("alias")),
+                ),
+            )
+            .subcommand(
+                Command::new("cp")
+                    .arg(Arg::new("src"))
+                    .arg(Arg::new("target")),
+            );
+        cmd.build();
+        cmd
+    }
+
+    fn words(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    fn positional(line: &str) -> Option<(Vec<String>, String)> {
+        current_positional(&test_command(), &words(line))
+    }
+
+    #[test]
+    fn test_current_positional() {
+        let ls = Some((words("ls"), "path".to_string()));
+        assert_eq!(positional("ls"), ls);
+        assert_eq!(positional("--json ls -r"), ls);
+        assert_eq!(positional("ls --rewind 1d"), ls);
+        assert_eq!(positional("ls --rewind=1d -C dir"), ls);
+        assert_eq!(positional("ls -rC dir"), ls);
+        // 正在输入选项的值
+        assert_eq!(positional("ls --rewind"), None);
+        assert_eq!(positional("ls a/b"), None);
+
+        assert_eq!(
+            positional("alias rm"),
+            Some((words("alias remove"), "alias".to_string()))
+        );
+        assert_eq!(
+            positional("cp a/b/c"),
+            Some((words("cp"), "target".to_string()))
+        );
+        assert_eq!(positional(""), None);
+    }
+
+    #[test]
+    fn test_arg_kind() {
+        assert_eq!(arg_kind(&words("cp"), "target"), Some(ArgKind::Remote));
+        assert_eq!(
+            arg_kind(&words("alias remove"), "alias"),
+            Some(ArgKind::Alias)
+        );
+        assert_eq!(arg_kind(&words("put"), "src"), None);
+    }
+}
@@ -8,6 +8,11 @@ use tokio::{fs::File, sync::Mutex};
 
 use indicatif::{ProgressBar, ProgressStyle};
 use tokio::io::AsyncReadExt;
+
+use crate::cmd::errors::CliError;
+use crate::cmd::globals;
+use crate::cmd::printer;
+use crate::cmd::put::CopyMessage;
 const CHUNK_SIZE: usize = 64 * 1024 * 1024; // 8 MB
 
 #[derive(clap::Args, Debug)]
@@ -30,21 +35,6 @@ pub struct CpOptions {
     #[arg(long, help = "Perform a dry run of the copy operation")]
     pub dry_run: bool,
 
-    #[arg(long, help = "Set the maximum upload limit (in KiB/s, MiB/s, etc.)")]
-    pub limit_upload: Option<String>,
-
-    #[arg(long, help = "Set the maximum download limit (in KiB/s, MiB/s, etc.)")]
-    pub limit_download: Option<String>,
-
-    #[arg(long, help = "Enable JSON lines formatted output")]
-    pub json: bool,
-
-    #[arg(long, help = "Enable debug output")]
-    pub debug: bool,
-
-    #[arg(long, help = "Disable SSL certificate verification")]
-    pub insecure: bool,
-
     #[arg(long, help = "Read object names from STDIN")]
     pub stdin: bool,
 
@@ -60,27 +50,9 @@ pub struct CpOptions {
     )]
     pub newer_than: Option<String>,
 
-    #[arg(
-        long,
-        help = "Resolve host[:port] to an IP address (e.g., minio.local:9000=10.10.75.1)"
-    )]
-    pub resolve: Option<String>,
-
     #[arg(long, help = "Bypass governance mode restrictions")]
     pub bypass: bool,
 
-    #[arg(long, help = "Specify the configuration directory")]
-    pub config_dir: Option<String>,
-
-    #[arg(long, short, help = "Disable progress bar display")]
-    pub quiet: bool,
-
-    #[arg(long, help = "Disable color output")]
-    pub no_color: bool,
-
-    #[arg(long, help = "Disable internal pager and print to raw stdout")]
-    pub disable_pager: bool,
-
     #[arg(long, help = "Remove non-current object versions")]
     pub non_current: bool,
 
@@ -97,8 +69,10 @@ pub struct CpOptions {
     pub dangerous: bool,
 }
 
-pub async fn handle_cp_command(opt: &CpOptions) -> Result<(), Box<dyn std::error::Error>> {
-    cp(opt).await
+pub async fn handle_cp_command(opt: &CpOptions) -> Result<(), CliError> {
+    cp(opt)
+        .await
+        .map_err(|err| err.context(format!("Unable to copy `{}`", opt.src)))
 }
 
 fn split_first_part(input: &str) -> (&str, &str) {
@@ -167,17 +141,23 @@ fn generate_s3_key(src: &str, target: &str) -> Result<(String, String, String),
     Ok((alias, bucket, key))
 }
 
-pub async fn cp(opt: &CpOptions) -> Result<(), Box<dyn std::error::Error>> {
+pub async fn cp(opt: &CpOptions) -> Result<(), CliError> {
     if opt.src.is_empty() {
-        return Err("Path is empty".into());
+        return Err(CliError::InvalidArgument("path is empty".to_string()));
     }
-    let (alias, bucket, key) = generate_s3_key(&opt.src, &opt.target)?;
+    let (alias, bucket, key) =
+        generate_s3_key(&opt.src, &opt.target).map_err(CliError::InvalidArgument)?;
     let cli = crate::s3::client::get_s3client_from_alias(&alias)?;
-    println!("keys is {}", key);
 
     let mut file = File::open(&opt.src).await?;
     let file_size = file.metadata().await?.len();
-    let progress_bar = ProgressBar::new(file_size);
+    // -q 或 --json 时不显示进度条
+    let flags = globals::global_flags();
+    let progress_bar = if flags.quiet || flags.json {
+        ProgressBar::hidden()
+    } else {
+        ProgressBar::new(file_size)
+    };
     let progress_style = ProgressStyle::default_bar()
 	.template("{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec}, {eta})")
 	.expect("Failed to create progress style") // Unwrap or handle the error here
@@ -194,7 +174,7 @@ pub async fn cp(opt: &CpOptions) -> Result<(), Box<dyn std::error::Error>> {
 
     let upload_id = create_resp
         .upload_id()
-        .expect("Failed to get upload ID")
+        .ok_or_else(|| CliError::Other("server did not return an upload ID".to_string()))?
         .to_string();
 
     let mut offset = 0;
@@ -221,8 +201,6 @@ pub async fn cp(opt: &CpOptions) -> Result<(), Box<dyn std::error::Error>> {
             break;
         }
 
-        println!("size is {}", total_bytes_read);
-
         let cli = Arc::clone(&s3_client);
         let upload_id = upload_id.clone();
         let bucket = bucket.clone();
@@ -242,10 +220,12 @@ pub async fn cp(opt: &CpOptions) -> Result<(), Box<dyn std::error::Error>> {
                 .part_number(current_part_number)
                 .body(ByteStream::from(buffer))
                 .send()
-                .await
-                .expect("Failed to upload part");
+                .await?;
 
-            let e_tag = part_resp.e_tag().expect("Failed to get ETag").to_string();
+            let e_tag = part_resp
+                .e_tag()
+                .ok_or_else(|| CliError::Other("server did not return an ETag".to_string()))?
+                .to_string();
             progress_bar.inc(total_bytes_read as u64);
 
             let mut completed_parts_lock = completed_parts.lock().await;
@@ -255,6 +235,7 @@ pub async fn cp(opt: &CpOptions) -> Result<(), Box<dyn std::error::Error>> {
                     .set_e_tag(Some(e_tag))
                     .build(),
             );
+            Ok::<(), CliError>(())
         });
 
         handles.push(handle);
@@ -262,8 +243,26 @@ pub async fn cp(opt: &CpOptions) -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Wait for all uploads to finish
+    // 任一分片上传失败时取消本次上传，避免服务端残留未完成的分片
+    let mut result = Ok(());
     for handle in handles {
-        handle.await?;
+        let ret = handle
+            .await
+            .map_err(|err| CliError::Other(err.to_string()))
+            .and_then(|ret| ret);
+        if result.is_ok() {
+            result = ret;
+        }
+    }
+    if let Err(err) = result {
+        let _ = s3_client
+            .abort_multipart_upload()
+            .bucket(&bucket)
+            .key(&key)
+            .upload_id(&upload_id)
+            .send()
+            .await;
+        return Err(err);
     }
 
     // Complete the multipart upload
@@ -276,13 +275,19 @@ pub async fn cp(opt: &CpOptions) -> Result<(), Box<dyn std::error::Error>> {
 
     s3_client
         .complete_multipart_upload()
-        .bucket(bucket)
-        .key(key)
+        .bucket(&bucket)
+        .key(&key)
         .upload_id(upload_id)
         .multipart_upload(completed_upload)
         .send()
         .await?;
 
-    progress_bar.finish_with_message("Upload complete");
+    progress_bar.finish_and_clear();
+    printer::print_msg(&CopyMessage {
+        status: "success".to_string(),
+        source: opt.src.clone(),
+        target: format!("{}/{}/{}", alias, bucket, key),
+        size: file_size,
+    });
     Ok(())
 }
@@ -0,0 +1,261 @@
+use aws_sdk_s3::error::{ProvideErrorMetadata, SdkError};
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use super::suggest;
+use super::tofu::{self, CredentialError};
+
+// 命令失败时的退出码，0 表示成功，2 与 clap 参数解析失败时相同
+pub const EXIT_GENERIC: i32 = 1;
+pub const EXIT_INVALID_ARGUMENT: i32 = 2;
+pub const EXIT_ALIAS_NOT_FOUND: i32 = 3;
+pub const EXIT_AUTH_FAILURE: i32 = 4;
+pub const EXIT_NOT_FOUND: i32 = 5;
+pub const EXIT_ACCESS_DENIED: i32 = 6;
+pub const EXIT_NETWORK: i32 = 7;
+pub const EXIT_TLS: i32 = 8;
+pub const EXIT_PARTIAL_FAILURE: i32 = 9;
+
+// 显示在 --help 末尾
+pub const EXIT_CODES_HELP: &str = "EXIT CODES:
+  0  success
+  1  other error
+  2  invalid argument
+  3  alias not found
+  4  authentication failure
+  5  bucket, object or file not found
+  6  access denied
+  7  network error
+  8  TLS error
+  9  partial failure, some of the operations failed";
+
+// 命令返回的错误，决定退出码以及 --json 时输出的错误类型
+#[derive(Debug)]
+pub enum CliError {
+    AliasNotFound(String),
+    AuthFailure(String),
+    NotFound(String),
+    AccessDenied(String),
+    Network(String),
+    Tls(String),
+    InvalidArgument(String),
+    PartialFailure(String),
+    Other(String),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::AliasNotFound(_) => EXIT_ALIAS_NOT_FOUND,
+            CliError::AuthFailure(_) => EXIT_AUTH_FAILURE,
+            CliError::NotFound(_) => EXIT_NOT_FOUND,
+            CliError::AccessDenied(_) => EXIT_ACCESS_DENIED,
+            CliError::Network(_) => EXIT_NETWORK,
+            CliError::Tls(_) => EXIT_TLS,
+            CliError::InvalidArgument(_) => EXIT_INVALID_ARGUMENT,
+            CliError::PartialFailure(_) => EXIT_PARTIAL_FAILURE,
+            CliError::Other(_) => EXIT_GENERIC,
+        }
+    }
+
+    // --json 输出中的 error.type
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CliError::AliasNotFound(_) => "aliasNotFound",
+            CliError::AuthFailure(_) => "authFailure",
+            CliError::NotFound(_) => "notFound",
+            CliError::AccessDenied(_) => "accessDenied",
+            CliError::Network(_) => "network",
+            CliError::Tls(_) => "tls",
+            CliError::InvalidArgument(_) => "invalidArgument",
+            CliError::PartialFailure(_) => "partialFailure",
+            CliError::Other(_) => "other",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            CliError::AliasNotFound(msg)
+            | CliError::AuthFailure(msg)
+            | CliError::NotFound(msg)
+            | CliError::AccessDenied(msg)
+            | CliError::Network(msg)
+            | CliError::Tls(msg)
+            | CliError::InvalidArgument(msg)
+            | CliError::PartialFailure(msg)
+            | CliError::Other(msg) => msg,
+        }
+    }
+
+    // 修改错误信息，保留错误类型
+    pub fn map_message(self, f: impl FnOnce(String) -> String) -> Self {
+        match self {
+            CliError::AliasNotFound(msg) => CliError::AliasNotFound(f(msg)),
+            CliError::AuthFailure(msg) => CliError::AuthFailure(f(msg)),
+            CliError::NotFound(msg) => CliError::NotFound(f(msg)),
+            CliError::AccessDenied(msg) => CliError::AccessDenied(f(msg)),
+            CliError::Network(msg) => CliError::Network(f(msg)),
+            CliError::Tls(msg) => CliError::Tls(f(msg)),
+            CliError::InvalidArgument(msg) => CliError::InvalidArgument(f(msg)),
+            CliError::PartialFailure(msg) => CliError::PartialFailure(f(msg)),
+            CliError::Other(msg) => CliError::Other(f(msg)),
+        }
+    }
+
+    // 在错误信息前加上正在执行的操作，例如 "Unable to make bucket `x`: ..."
+    pub fn context(self, ctx: impl fmt::Display) -> Self {
+        self.map_message(|msg| format!("{}: {}", ctx, msg))
+    }
+}
+
+// known 为已有的别名，用来提示相近的别名
+pub fn alias_not_found<'a>(alias: &str, known: impl IntoIterator<Item = &'a String>) -> CliError {
+    let closest = suggest::find_closest(alias, known.into_iter().map(String::as_str));
+    CliError::AliasNotFound(format!(
+        "alias `{}` not found{}",
+        alias,
+        suggest::did_you_mean(&closest)
+    ))
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl Error for CliError {}
+
+impl<E> From<SdkError<E, HttpResponse>> for CliError
+where
+    E: ProvideErrorMetadata + Error + 'static,
+{
+    fn from(err: SdkError<E, HttpResponse>) -> Self {
+        let msg = tofu::describe_sdk_error(&err);
+        match err {
+            SdkError::ServiceError(ref service_err) => {
+                match (
+                    service_err.err().code(),
+                    service_err.raw().status().as_u16(),
+                ) {
+                    (
+                        Some(
+                            "InvalidAccessKeyId"
+                            | "SignatureDoesNotMatch"
+                            | "ExpiredToken"
+                            | "InvalidToken"
+                            | "RequestTimeTooSkewed",
+                        ),
+                        _,
+                    ) => CliError::AuthFailure(msg),
+                    (Some("AccessDenied"), _) | (None, 403) => CliError::AccessDenied(msg),
+                    (Some("NoSuchBucket" | "NoSuchKey" | "NoSuchUpload" | "NotFound"), _)
+                    | (None, 404) => CliError::NotFound(msg),
+                    (Some("InvalidBucketName" | "InvalidArgument"), _) => {
+                        CliError::InvalidArgument(msg)
+                    }
+                    _ => CliError::Other(msg),
+                }
+            }
+            SdkError::DispatchFailure(ref failure) => {
+                if tofu::is_tls_error(&err) {
+                    CliError::Tls(msg)
+                } else if failure.is_io() || failure.is_timeout() {
+                    CliError::Network(msg)
+                } else {
+                    CliError::Other(msg)
+                }
+            }
+            SdkError::TimeoutError(_) => CliError::Network(msg),
+            _ => CliError::Other(msg),
+        }
+    }
+}
+
+impl From<CredentialError> for CliError {
+    fn from(err: CredentialError) -> Self {
+        let msg = err.to_string();
+        match err {
+            CredentialError::InvalidAccessKey
+            | CredentialError::BadSignature
+            | CredentialError::ClockSkew => CliError::AuthFailure(msg),
+            CredentialError::Unreachable(_) => CliError::Network(msg),
+            CredentialError::Tls(_) => CliError::Tls(msg),
+            CredentialError::Other(_) => CliError::Other(msg),
+        }
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(err: io::Error) -> Self {
+        let msg = err.to_string();
+        match err.kind() {
+            io::ErrorKind::NotFound => CliError::NotFound(msg),
+            io::ErrorKind::PermissionDenied => CliError::AccessDenied(msg),
+            io::ErrorKind::InvalidInput | io::ErrorKind::InvalidData => {
+                CliError::InvalidArgument(msg)
+            }
+            _ => CliError::Other(msg),
+        }
+    }
+}
+
+// admin 命令通过 reqwest 直接请求服务端
+impl From<reqwest::Error> for CliError {
+    fn from(err: reqwest::Error) -> Self {
+        let msg = tofu::error_chain(&err);
+        if tofu::is_tls_error(&err) {
+            CliError::Tls(msg)
+        } else if err.is_connect() || err.is_timeout() {
+            CliError::Network(msg)
+        } else {
+            CliError::Other(msg)
+        }
+    }
+}
+
+// 配置、别名等模块仍返回 Box<dyn Error>，按实际的错误类型归类
+impl From<Box<dyn Error>> for CliError {
+    fn from(err: Box<dyn Error>) -> Self {
+        let err = match err.downcast::<CliError>() {
+            Ok(err) => return *err,
+            Err(err) => err,
+        };
+        let err = match err.downcast::<CredentialError>() {
+            Ok(err) => return (*err).into(),
+            Err(err) => err,
+        };
+        let err = match err.downcast::<reqwest::Error>() {
+            Ok(err) => return (*err).into(),
+            Err(err) => err,
+        };
+        match err.downcast::<io::Error>() {
+            Ok(err) => (*err).into(),
+            Err(err) => CliError::Other(err.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_boxed_error() {
+        let err: Box<dyn Error> = Box::new(CliError::AliasNotFound("no alias".to_string()));
+        assert_eq!(CliError::from(err).exit_code(), EXIT_ALIAS_NOT_FOUND);
+
+        let err: Box<dyn Error> = Box::new(CredentialError::BadSignature);
+        assert_eq!(CliError::from(err).exit_code(), EXIT_AUTH_FAILURE);
+
+        let err: Box<dyn Error> = Box::new(io::Error::from(io::ErrorKind::NotFound));
+        assert_eq!(CliError::from(err).exit_code(), EXIT_NOT_FOUND);
+
+        let err: Box<dyn Error> = "Path is empty".into();
+        let err = CliError::from(err).context("Unable to list");
+        assert_eq!(err.exit_code(), EXIT_GENERIC);
+        assert_eq!(err.to_string(), "Unable to list: Path is empty");
+    }
+}
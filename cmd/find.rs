@@ -1,4 +1,3 @@
-use anyhow::Result;
 use async_trait::async_trait;
 use aws_sdk_s3::Client as S3Client;
 use chrono::{Local, Utc};
@@ -21,6 +20,9 @@ const PRINT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S %Z";
 use glob::Pattern;
 use tokio::sync::mpsc;
 
+use crate::cmd::errors::CliError;
+use crate::cmd::globals;
+use crate::cmd::printer::{self, Message};
 use crate::s3;
 
 pub struct LsOptions {
@@ -42,7 +44,7 @@ struct S3ClientWrapper {
 
 #[async_trait]
 trait Client {
-    async fn list(&self, options: &LsOptions) -> mpsc::Receiver<ContentMessage>;
+    async fn list(&self, options: &LsOptions) -> mpsc::Receiver<Result<ContentMessage, CliError>>;
     fn get_url(&self) -> String;
 }
 
@@ -52,18 +54,19 @@ enum ListResult {
 }
 #[async_trait]
 impl Client for S3ClientWrapper {
-    async fn list(&self, options: &LsOptions) -> mpsc::Receiver<ContentMessage> {
+    async fn list(&self, options: &LsOptions) -> mpsc::Receiver<Result<ContentMessage, CliError>> {
         let (tx, rx) = mpsc::channel(100);
         let client = self.client.clone();
         let bucket = self.bucket.clone();
 
         let delimiter = if options.recursive { "" } else { "/" };
+        let url = self.get_url();
 
         tokio::spawn(async move {
             let mut continuation_token = None;
 
             loop {
-                let result: Result<ListResult> = if !bucket.is_empty() {
+                let result: Result<ListResult, CliError> = if !bucket.is_empty() {
                     client
                         .list_objects_v2()
                         .bucket(&bucket)
@@ -72,14 +75,14 @@ impl Client for S3ClientWrapper {
                         .send()
                         .await
                         .map(ListResult::Objects)
-                        .map_err(anyhow::Error::from) // 使用 `anyhow` 将错误包装
+                        .map_err(CliError::from)
                 } else {
                     client
                         .list_buckets()
                         .send()
                         .await
                         .map(ListResult::Buckets)
-                        .map_err(anyhow::Error::from) // 使用 `anyhow` 将错误包装
+                        .map_err(CliError::from)
                 };
 
                 match result {
@@ -89,12 +92,13 @@ impl Client for S3ClientWrapper {
                                 //if ret.buckets() {
                                 for bucket in ret.buckets() {
                                     let _ = tx
-                                        .send(ContentMessage {
+                                        .send(Ok(ContentMessage {
                                             key: bucket.name().unwrap_or_default().to_string(), // bucket 名称
-                                            size: 0,            // bucket 没有大小
+                                            size: 0, // bucket 没有大小
+                                            filetype: "folder".to_string(),
                                             is_directory: true, // 标识为目录
                                             ..ContentMessage::new()
-                                        })
+                                        }))
                                         .await;
                                 }
                                 //}
@@ -103,18 +107,18 @@ impl Client for S3ClientWrapper {
                             }
                             ListResult::Objects(objs) => {
                                 if !objs.common_prefixes().is_empty() {
-                                    println!("-------------");
                                     for prefix in objs.common_prefixes() {
                                         let _ = tx
-                                            .send(ContentMessage {
+                                            .send(Ok(ContentMessage {
                                                 key: prefix
                                                     .prefix()
                                                     .unwrap_or_default()
                                                     .to_string(),
-                                                size: 0,            // 目录没有大小
+                                                size: 0, // 目录没有大小
+                                                filetype: "folder".to_string(),
                                                 is_directory: true, // 自定义字段标识为目录
                                                 ..ContentMessage::new()
-                                            })
+                                            }))
                                             .await;
                                     }
                                 }
@@ -123,7 +127,7 @@ impl Client for S3ClientWrapper {
                                 if !objs.contents().is_empty() {
                                     for object in objs.contents() {
                                         let _ = tx
-                                            .send(ContentMessage {
+                                            .send(Ok(ContentMessage {
                                                 key: object.key().unwrap_or_default().to_string(),
                                                 size: object.size().unwrap(),
                                                 storage_class: object
@@ -131,7 +135,7 @@ impl Client for S3ClientWrapper {
                                                     .map(|sc| sc.as_str().to_string()),
                                                 is_directory: false, // 自定义字段标识为文件
                                                 ..ContentMessage::new()
-                                            })
+                                            }))
                                             .await;
                                     }
                                 }
@@ -144,13 +148,8 @@ impl Client for S3ClientWrapper {
                         }
                     }
                     Err(error) => {
-                        eprintln!("Error listing objects: {:?}", error);
                         let _ = tx
-                            .send(ContentMessage {
-                                key: "".to_string(),
-                                size: 0,
-                                ..ContentMessage::new()
-                            })
+                            .send(Err(error.context(format!("Unable to list `{}`", url))))
                             .await;
                         break;
                     }
@@ -228,6 +227,13 @@ impl ContentMessage {
         serde_json::to_string_pretty(self).unwrap_or_else(|_| "{}".to_string())
     }
 }
+
+impl Message for ContentMessage {
+    fn to_text(&self) -> String {
+        self.key.clone()
+    }
+}
+
 const PRINT_DATE: &str = "%Y-%m-%d %H:%M:%S %Z";
 impl std::fmt::Display for ContentMessage {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -335,51 +341,8 @@ pub struct FindOptions {
         help = "Match tags with RE2 regex pattern. Specify each with key=regex. MinIO server only."
     )]
     pub tags: Option<String>,
-
-    #[arg(
-        long,
-        short = 'C',
-        help = "Path to configuration folder (default: \"/home/ldy/.mc\")"
-    )]
-    pub config_dir: Option<String>,
-
-    #[arg(long, short = 'q', help = "Disable progress bar display")]
-    pub quiet: bool,
-
-    #[arg(long, help = "Disable mc internal pager and print to raw stdout")]
-    pub disable_pager: bool,
-
-    #[arg(long, help = "Disable color theme")]
-    pub no_color: bool,
-
-    #[arg(long, help = "Enable JSON lines formatted output")]
-    pub json: bool,
-
-    #[arg(long, help = "Enable debug output")]
-    pub debug: bool,
-
-    #[arg(
-        long,
-        help = "Resolves HOST[:PORT] to an IP address. Example: minio.local:9000=10.10.75.1"
-    )]
-    pub resolve: Option<String>,
-
-    #[arg(long, help = "Disable SSL certificate verification")]
-    pub insecure: bool,
-
-    #[arg(
-        long,
-        help = "Limits uploads to a maximum rate in KiB/s, MiB/s, GiB/s. (default: unlimited)"
-    )]
-    pub limit_upload: Option<String>,
-
-    #[arg(
-        long,
-        help = "Limits downloads to a maximum rate in KiB/s, MiB/s, GiB/s. (default: unlimited)"
-    )]
-    pub limit_download: Option<String>,
 }
-pub async fn handle_find_command(opt: &FindOptions) {
+pub async fn handle_find_command(opt: &FindOptions) -> Result<(), CliError> {
     //let _ = find(opt).await;
     let small: u64 = if opt.smaller.is_some() {
         match bytesize::ByteSize::from_str(&opt.smaller.as_ref().unwrap()) {
@@ -397,7 +360,7 @@ pub async fn handle_find_command(opt: &FindOptions) {
     } else {
         0
     };
-    let s3_client = s3::client::get_s3client_from_alias("minio").unwrap();
+    let s3_client = s3::client::get_s3client_from_alias("minio")?;
     let ctx = FindContext {
         clnt: Box::new(S3ClientWrapper {
             client: s3_client,
@@ -411,7 +374,7 @@ pub async fn handle_find_command(opt: &FindOptions) {
         max_depth: 32,
         larger_size: large,
         smaller_size: small,
-        print_fmt: Some("".to_string()),
+        print_fmt: opt.print.clone(),
         older_than: None,
         newer_than: None,
         watch: false,
@@ -422,7 +385,7 @@ pub async fn handle_find_command(opt: &FindOptions) {
         target_url: Some(opt.path.clone()),
         target_full_url: None,
     };
-    do_find(ctx.into()).await;
+    do_find(ctx.into()).await
 }
 
 fn split_first_part(input: &str) -> (&str, &str) {
@@ -466,7 +429,7 @@ fn name_match(pattern: &str, path: &str) -> bool {
     let matched = match glob::Pattern::new(pattern) {
         Ok(glob_pattern) => glob_pattern.matches(path_base),
         Err(e) => {
-            eprintln!("Unable to match with input pattern: {:?}", e);
+            printer::print_error(&format!("Unable to match with input pattern: {}", e));
             return false;
         }
     };
@@ -654,7 +617,6 @@ fn match_regex_maps(m: &HashMap<String, Option<Regex>>, v: &HashMap<String, Stri
 }
 
 fn match_find(ctx: &FindContext, file_content: &ContentMessage) -> bool {
-    println!("key is 1: {}:", file_content.key.clone());
     let mut match_result = true;
     let mut prefix_path = ctx.target_url.clone();
 
@@ -724,7 +686,6 @@ fn match_find(ctx: &FindContext, file_content: &ContentMessage) -> bool {
         match_result &= match_regex_maps(&ctx.match_tags, &file_content.tags.as_ref().unwrap());
         //println!("key is 11: {}:", file_content.key.clone());
     }
-    println!("key is 13: {}:{}", file_content.key.clone(), match_result);
 
     match_result
 }
@@ -741,15 +702,17 @@ async fn find(ctx_ctx: &str, ctx: &FindContext, file_content: ContentMessage) {
         return;
     }
 
-    // If printFmt is specified, format the output string
-    if let Some(print_fmt) = &ctx.print_fmt {
-        let formatted_content = strings_replace(ctx, print_fmt, &file_content);
-        println!("Formatted output: {}", formatted_content);
-    }
+    print_find_result(ctx, &file_content);
+}
 
-    // Print the message with file content
-    //print_msg(FindMessage { file_content });
-    println!("{}", file_content);
+// 输出一条匹配结果，指定 --print 时输出格式化后的文本
+fn print_find_result(ctx: &FindContext, file_content: &ContentMessage) {
+    match &ctx.print_fmt {
+        Some(print_fmt) if !globals::global_flags().json => {
+            println!("{}", strings_replace(ctx, print_fmt, file_content));
+        }
+        _ => printer::print_msg(file_content),
+    }
 }
 
 fn strings_replace(ctx: &FindContext, args: &str, file_content: &ContentMessage) -> String {
@@ -806,7 +769,7 @@ fn strings_replace(ctx: &FindContext, args: &str, file_content: &ContentMessage)
     str
 }
 
-async fn do_find(ctx: Arc<FindContext>) -> Result<(), Box<dyn std::error::Error>> {
+async fn do_find(ctx: Arc<FindContext>) -> Result<(), CliError> {
     // Initialize list options
     let list_options = LsOptions {
         versions: false,
@@ -825,10 +788,7 @@ async fn do_find(ctx: Arc<FindContext>) -> Result<(), Box<dyn std::error::Error>
 
     // Iterate over content items
     while let Some(content) = content_stream.recv().await {
-        // if let Some(err) = content.err {
-        //     eprintln!("Error: {}", err.to_string());
-        //     continue;
-        // }
+        let content = content?;
 
         // Skip Glacier storage class items
         if let Some(storage_class) = &content.storage_class {
@@ -845,14 +805,14 @@ async fn do_find(ctx: Arc<FindContext>) -> Result<(), Box<dyn std::error::Error>
             size: content.size,
             metadata: content.metadata,
             tags: content.tags,
-            etag: "".to_string(),
-            status: "".to_string(),
-            filetype: "".to_string(),
-            url: Some("".to_string()),
-            is_delete_marker: Some(true),
-            version_ord: Some(0),
-            version_index: Some(0),
-            storage_class: Some("".to_string()),
+            etag: content.etag,
+            status: "success".to_string(),
+            filetype: content.filetype,
+            url: content.url,
+            is_delete_marker: content.is_delete_marker,
+            version_ord: content.version_ord,
+            version_index: content.version_index,
+            storage_class: content.storage_class,
             is_directory: content.is_directory,
         };
 
@@ -860,7 +820,6 @@ async fn do_find(ctx: Arc<FindContext>) -> Result<(), Box<dyn std::error::Error>
         if !match_find(&ctx, &file_content) {
             continue;
         }
-        println!("src key is: {}", file_content.key.clone());
 
         // Execute command or format the output
         // if let Some(exec_cmd) = &ctx.exec_cmd {
@@ -869,12 +828,7 @@ async fn do_find(ctx: Arc<FindContext>) -> Result<(), Box<dyn std::error::Error>
         //     continue;
         // }
 
-        if let Some(print_fmt) = &ctx.print_fmt {
-            let formatted_key = strings_replace(&ctx, print_fmt, &file_content);
-            println!("Formatted Key: {}", formatted_key);
-        } else {
-            println!("Key: {}", file_content.key);
-        }
+        print_find_result(&ctx, &file_content);
     }
 
     Ok(())
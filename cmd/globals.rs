@@ -0,0 +1,58 @@
+use once_cell::sync::OnceCell;
+
+use crate::s3::http::ResolveOverride;
+
+// 所有命令共用的全局参数，可以写在子命令的前面或后面
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct GlobalFlags {
+    #[arg(
+        long,
+        short = 'q',
+        global = true,
+        help = "disable progress bar display"
+    )]
+    pub quiet: bool,
+
+    #[arg(long, global = true, help = "disable color theme")]
+    pub no_color: bool,
+
+    #[arg(long, global = true, help = "enable JSON lines formatted output")]
+    pub json: bool,
+
+    #[arg(
+        long,
+        global = true,
+        help = "enable debug output, including HTTP requests and responses with credentials redacted"
+    )]
+    pub debug: bool,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "FILE",
+        help = "append debug output to FILE instead of stderr, implies --debug"
+    )]
+    pub log_file: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        help = "resolves HOST[:PORT] to an IP address. Example: minio.local:9000=10.10.75.1"
+    )]
+    pub resolve: Vec<ResolveOverride>,
+
+    #[arg(long, global = true, help = "disable SSL certificate verification")]
+    pub insecure: bool,
+}
+
+static GLOBAL_FLAGS: OnceCell<GlobalFlags> = OnceCell::new();
+
+// 解析命令行后设置一次
+pub fn set_global_flags(flags: GlobalFlags) {
+    let _ = GLOBAL_FLAGS.set(flags);
+}
+
+// 未设置时（例如单元测试中）使用默认值
+pub fn global_flags() -> &'static GlobalFlags {
+    GLOBAL_FLAGS.get_or_init(GlobalFlags::default)
+}
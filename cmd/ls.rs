@@ -1,11 +1,8 @@
-use crate::cmd::aliasremove::get_alias;
+use crate::cmd::errors::CliError;
 use crate::cmd::lsmain;
-use anyhow::Result;
+use crate::cmd::printer::{self, Message};
 use async_trait::async_trait;
-use aws_sdk_s3::config::Credentials;
-use aws_sdk_s3::config::Region;
 use aws_sdk_s3::Client as S3Client;
-use aws_sdk_s3::Config;
 use chrono::DateTime;
 use chrono::Local;
 use human_bytes::human_bytes;
@@ -19,22 +16,31 @@ const PRINT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S %Z";
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct ContentMessage {
     status: String,
+    #[serde(rename = "type")]
     filetype: String,
+    #[serde(rename = "lastModified")]
     time: DateTime<Local>,
     size: i64,
     key: String,
     etag: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     url: Option<String>,
 
+    #[serde(rename = "versionId", skip_serializing_if = "Option::is_none")]
     version_id: Option<String>,
+    #[serde(rename = "versionOrdinal", skip_serializing_if = "Option::is_none")]
     version_ord: Option<i32>,
+    #[serde(rename = "versionIndex", skip_serializing_if = "Option::is_none")]
     version_index: Option<i32>,
+    #[serde(rename = "isDeleteMarker", skip_serializing_if = "Option::is_none")]
     is_delete_marker: Option<bool>,
+    #[serde(rename = "storageClass", skip_serializing_if = "Option::is_none")]
     storage_class: Option<String>,
 
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
     metadata: HashMap<String, String>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
     tags: HashMap<String, String>,
-    is_directory: bool,
 }
 
 impl ContentMessage {
@@ -54,11 +60,12 @@ impl ContentMessage {
             storage_class: None,
             metadata: HashMap::new(),
             tags: HashMap::new(),
-            is_directory: false,
         }
     }
+}
 
-    fn to_string(&self) -> String {
+impl Message for ContentMessage {
+    fn to_text(&self) -> String {
         format!(
             "[{}] {:>10} {}",
             self.time.format(PRINT_DATE_FORMAT),
@@ -67,9 +74,25 @@ impl ContentMessage {
             self.key
         )
     }
+}
+
+// --summarize 时最后输出的统计信息
+#[derive(Serialize)]
+struct SummaryMessage {
+    status: String,
+    #[serde(rename = "totalObjects")]
+    total_objects: i64,
+    #[serde(rename = "totalSize")]
+    total_size: i64,
+}
 
-    fn to_json(&self) -> String {
-        serde_json::to_string_pretty(self).unwrap_or_else(|_| "{}".to_string())
+impl Message for SummaryMessage {
+    fn to_text(&self) -> String {
+        format!(
+            "\nTotal Size: {}\nTotal Objects: {}",
+            human_bytes(self.total_size as f64),
+            self.total_objects
+        )
     }
 }
 
@@ -81,7 +104,10 @@ struct S3ClientWrapper {
 
 #[async_trait]
 trait Client {
-    async fn list(&self, options: &lsmain::LsOptions) -> mpsc::Receiver<ContentMessage>;
+    async fn list(
+        &self,
+        options: &lsmain::LsOptions,
+    ) -> mpsc::Receiver<Result<ContentMessage, CliError>>;
     fn get_url(&self) -> String;
 }
 
@@ -92,18 +118,22 @@ enum ListResult {
 
 #[async_trait]
 impl Client for S3ClientWrapper {
-    async fn list(&self, options: &lsmain::LsOptions) -> mpsc::Receiver<ContentMessage> {
+    async fn list(
+        &self,
+        options: &lsmain::LsOptions,
+    ) -> mpsc::Receiver<Result<ContentMessage, CliError>> {
         let (tx, rx) = mpsc::channel(100);
         let client = self.client.clone();
         let bucket = self.bucket.clone();
 
         let delimiter = if options.recursive { "" } else { "/" };
+        let url = self.get_url();
 
         tokio::spawn(async move {
             let mut continuation_token = None;
 
             loop {
-                let result: Result<ListResult> = if !bucket.is_empty() {
+                let result: Result<ListResult, CliError> = if !bucket.is_empty() {
                     client
                         .list_objects_v2()
                         .bucket(&bucket)
@@ -112,14 +142,14 @@ impl Client for S3ClientWrapper {
                         .send()
                         .await
                         .map(ListResult::Objects)
-                        .map_err(anyhow::Error::from) // 使用 `anyhow` 将错误包装
+                        .map_err(CliError::from)
                 } else {
                     client
                         .list_buckets()
                         .send()
                         .await
                         .map(ListResult::Buckets)
-                        .map_err(anyhow::Error::from) // 使用 `anyhow` 将错误包装
+                        .map_err(CliError::from)
                 };
 
                 match result {
@@ -129,12 +159,12 @@ impl Client for S3ClientWrapper {
                                 //if ret.buckets() {
                                 for bucket in ret.buckets() {
                                     let _ = tx
-                                        .send(ContentMessage {
+                                        .send(Ok(ContentMessage {
                                             key: bucket.name().unwrap_or_default().to_string(), // bucket 名称
-                                            size: 0,            // bucket 没有大小
-                                            is_directory: true, // 标识为目录
+                                            size: 0, // bucket 没有大小
+                                            filetype: "folder".to_string(),
                                             ..ContentMessage::new()
-                                        })
+                                        }))
                                         .await;
                                 }
                                 //}
@@ -145,15 +175,15 @@ impl Client for S3ClientWrapper {
                                 if !objs.common_prefixes().is_empty() {
                                     for prefix in objs.common_prefixes() {
                                         let _ = tx
-                                            .send(ContentMessage {
+                                            .send(Ok(ContentMessage {
                                                 key: prefix
                                                     .prefix()
                                                     .unwrap_or_default()
                                                     .to_string(),
-                                                size: 0,            // 目录没有大小
-                                                is_directory: true, // 自定义字段标识为目录
+                                                size: 0, // 目录没有大小
+                                                filetype: "folder".to_string(),
                                                 ..ContentMessage::new()
-                                            })
+                                            }))
                                             .await;
                                     }
                                 }
@@ -162,15 +192,14 @@ impl Client for S3ClientWrapper {
                                 if !objs.contents().is_empty() {
                                     for object in objs.contents() {
                                         let _ = tx
-                                            .send(ContentMessage {
+                                            .send(Ok(ContentMessage {
                                                 key: object.key().unwrap_or_default().to_string(),
                                                 size: object.size().unwrap(),
                                                 storage_class: object
                                                     .storage_class()
                                                     .map(|sc| sc.as_str().to_string()),
-                                                is_directory: false, // 自定义字段标识为文件
                                                 ..ContentMessage::new()
-                                            })
+                                            }))
                                             .await;
                                     }
                                 }
@@ -183,13 +212,8 @@ impl Client for S3ClientWrapper {
                         }
                     }
                     Err(error) => {
-                        eprintln!("Error listing objects: {:?}", error);
                         let _ = tx
-                            .send(ContentMessage {
-                                key: "".to_string(),
-                                size: 0,
-                                ..ContentMessage::new()
-                            })
+                            .send(Err(error.context(format!("Unable to list `{}`", url))))
                             .await;
                         break;
                     }
@@ -205,24 +229,34 @@ impl Client for S3ClientWrapper {
     }
 }
 
-async fn do_list(client: impl Client + Send + Sync, options: &lsmain::LsOptions) {
+async fn do_list(
+    client: impl Client + Send + Sync,
+    options: &lsmain::LsOptions,
+) -> Result<(), CliError> {
     let mut last_path = String::new();
     let mut total_size = 0;
     let mut total_objects = 0;
 
     let mut receiver = client.list(options).await;
     while let Some(content) = receiver.recv().await {
+        let content = content?;
         total_size += content.size;
         total_objects += 1;
 
         if last_path != content.key {
-            println!("{}", content.to_string());
+            printer::print_msg(&content);
             last_path = content.key.clone();
         }
     }
 
-    println!("Total Size: {} bytes", total_size);
-    println!("Total Objects: {}", total_objects);
+    if options.summarize {
+        printer::print_msg(&SummaryMessage {
+            status: "success".to_string(),
+            total_objects,
+            total_size,
+        });
+    }
+    Ok(())
 }
 
 //#[tokio::main]
@@ -234,47 +268,23 @@ fn split_first_part(input: &str) -> (&str, &str) {
     (first_part, rest_part)
 }
 
-pub async fn ls(opt: &lsmain::LsOptions) -> Result<(), Box<dyn std::error::Error>> {
+pub async fn ls(opt: &lsmain::LsOptions) -> Result<(), CliError> {
     if opt.path.is_empty() {
-        println!("path is empty");
-        return Err("Path is empty".into());
+        return Err(CliError::InvalidArgument("path is empty".to_string()));
     }
     let (alias, key) = split_first_part(&opt.path);
 
-    //let ret = get_alias(alias);
-    if let Ok(conf) = get_alias(alias) {
-        println!("get config {} suc", alias);
-        let credentials = Credentials::new(conf.access_key, conf.secret_key, None, None, "");
-        let region = Region::new("us-east-1".to_string());
-
-        let config = Config::builder()
-            .region(region)
-            .endpoint_url(conf.url.to_string())
-            .credentials_provider(credentials)
-            .behavior_version(aws_sdk_s3::config::BehaviorVersion::latest())
-            .build();
-
-        let s3_client = S3Client::from_conf(config);
-        //let bucket = "xxxxx".to_string();
+    let s3_client = crate::s3::client::get_s3client_from_alias(alias)?;
+    let (bucket, key) = split_first_part(key);
 
-        let (bucket, key) = split_first_part(key);
+    tracing::debug!("bucket is {}, key is {}", bucket, key);
 
-        println!("bucket is {}", bucket);
-        println!("key is {}", key);
+    let client = S3ClientWrapper {
+        client: s3_client,
+        bucket: bucket.to_string(),
+    };
 
-        let client = S3ClientWrapper {
-            client: s3_client,
-            bucket: bucket.to_string(),
-        };
-
-        do_list(client, opt).await;
-        println!("------------------");
-
-        Ok(())
-    } else {
-        println!("path is empty");
-        return Err("alias is null".into());
-    }
+    do_list(client, opt).await
 
     // let s3_client = S3Client::from_conf(config);
     // let bucket = "xxxxx".to_string();
@@ -3,20 +3,45 @@
 use clap::command;
 
 use super::{
-    admin, alias, cp, find, lsmain, mb,
+    admin, alias, certexpiry, completion, config, configx, cp, errors, find, globals, lsmain, mb,
+    printer,
     put::{self},
     rb::{self},
-    rm,
+    rm, suggest,
 };
+use std::io::IsTerminal;
 
 // static APPNAME: &str = "rustfs-cli [FLAGS] COMMAND [COMMAND FLAGS | -h] [ARGUMENTS...]";
 static ABOUT: &str = "manager client for rustfs and minio";
-use clap::Parser;
+use clap::error::{ContextKind, ContextValue, ErrorKind};
+use clap::{CommandFactory, Parser};
 
 #[derive(Parser)]
 #[command(name = "rustfs-cli")]
 #[command(about = ABOUT)]
+#[command(after_help = errors::EXIT_CODES_HELP)]
 struct Cli {
+    #[arg(
+        long,
+        short = 'C',
+        global = true,
+        env = config::RC_ENV_CONFIG_DIR,
+        help = "path to configuration folder (default: ~/.rustfs-cli)"
+    )]
+    config_dir: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        env = certexpiry::RC_ENV_CERT_EXPIRY_WARN_DAYS,
+        default_value_t = certexpiry::DEFAULT_CERT_EXPIRY_WARN_DAYS,
+        help = "warn when a server certificate expires within this many days (0 disables)"
+    )]
+    cert_expiry_warn_days: u64,
+
+    #[command(flatten)]
+    global: globals::GlobalFlags,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -48,63 +73,97 @@ enum Commands {
     Cp(cp::CpOptions),
     #[command(about = "search for objects")]
     Find(find::FindOptions),
+    #[command(about = "generate shell completion scripts")]
+    Completion(completion::CompletionOptions),
+}
+
+// 加载 MC_CONFIG_FILE 中定义的别名，并将旧版本的配置文件迁移到当前格式
+fn load_config() -> Result<(), errors::CliError> {
+    config::load_env_config_file().map_err(|e| {
+        errors::CliError::from(e).context(format!(
+            "Unable to parse `{}`",
+            std::env::var(config::MC_ENV_CONFIG_FILE).unwrap_or_default()
+        ))
+    })?;
+    configx::migrate_config()
+        .map_err(|e| errors::CliError::from(e).context("Unable to migrate config file"))
 }
 
 async fn register_app() {
-    let cli = Cli::parse();
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some(completion::COMPLETE_COMMAND) {
+        completion::handle_complete_command(Cli::command(), &args[2..]).await;
+        return;
+    }
+
+    let cli = match Cli::try_parse_from(&args) {
+        Ok(cli) => cli,
+        // 子命令不存在时按 Damerau-Levenshtein 距离提示相近的命令
+        Err(err) if err.kind() == ErrorKind::InvalidSubcommand => {
+            let name = match err.get(ContextKind::InvalidSubcommand) {
+                Some(ContextValue::String(name)) => name.clone(),
+                _ => err.exit(),
+            };
+            let msg = suggest::command_not_found(&mut Cli::command(), &args, &name);
+            // 解析失败时全局参数尚未设置
+            globals::set_global_flags(globals::GlobalFlags {
+                json: args.iter().any(|arg| arg == "--json"),
+                ..Default::default()
+            });
+            printer::print_cli_error(&errors::CliError::InvalidArgument(msg));
+            std::process::exit(errors::EXIT_INVALID_ARGUMENT);
+        }
+        Err(err) => err.exit(),
+    };
+
+    if let Some(config_dir) = &cli.config_dir {
+        config::set_mc_config_dir(config_dir.clone());
+    }
+
+    // --no-color 或输出不是终端时不使用颜色
+    if cli.global.no_color || cli.global.json || !std::io::stdout().is_terminal() {
+        colored::control::set_override(false);
+    }
+    let mut flags = cli.global.clone();
+    flags.debug |= flags.log_file.is_some();
+    if let Err(err) = printer::init_debug_log(&flags) {
+        let err = errors::CliError::from(err).context(format!(
+            "Unable to open log file `{}`",
+            flags.log_file.as_deref().unwrap_or_default()
+        ));
+        printer::print_cli_error(&err);
+        std::process::exit(err.exit_code());
+    }
+    globals::set_global_flags(flags);
 
-    match &cli.command {
+    // 在设置全局参数之后加载，错误同样按 --json 输出并按错误类型返回退出码
+    if let Err(err) = load_config() {
+        printer::print_cli_error(&err);
+        std::process::exit(err.exit_code());
+    }
+
+    let result = match &cli.command {
         Commands::Alias { subcommand } => {
             //handle_config_commands(subcommand);
-            alias::handle_alias_commands(subcommand).await;
-        }
-        Commands::Admin { subcommand } => {
-            admin::handle_admin_commands(subcommand).await;
-        }
-        Commands::Ls(opt) => {
-            println!("xxxxxxxxxxx");
-            lsmain::handle_ls_commands(opt).await;
-        }
-        Commands::Mb(opt) => {
-            mb::handle_mb_command(opt).await;
-        }
-        Commands::Rb(opt) => {
-            rb::handle_rb_command(opt).await;
-        }
-        Commands::Put(opt) => {
-            match put::handle_put_command(opt).await {
-                Ok(_) => {
-                    // Handle the success case here if needed
-                    // For example, you might want to log a success message
-                }
-                Err(e) => {
-                    eprintln!("Error: {:?}", e); // Log the error or take other actions
-                }
-            }
-        }
-        Commands::Rm(opt) => {
-            match rm::handle_rm_command(opt).await {
-                Ok(_) => {
-                    println!("remove successed");
-                }
-                Err(e) => {
-                    eprintln!("Error: {:?}", e); // Log the error or take other actions
-                }
-            }
-        }
-        Commands::Cp(opt) => {
-            match cp::handle_cp_command(opt).await {
-                Ok(_) => {
-                    println!("remove successed");
-                }
-                Err(e) => {
-                    eprintln!("Error: {:?}", e); // Log the error or take other actions
-                }
-            }
-        }
-        Commands::Find(opt) => {
-            find::handle_find_command(opt).await;
+            alias::handle_alias_commands(subcommand).await
         }
+        Commands::Admin { subcommand } => admin::handle_admin_commands(subcommand).await,
+        Commands::Ls(opt) => lsmain::handle_ls_commands(opt).await,
+        Commands::Mb(opt) => mb::handle_mb_command(opt).await,
+        Commands::Rb(opt) => rb::handle_rb_command(opt).await,
+        Commands::Put(opt) => put::handle_put_command(opt).await,
+        Commands::Rm(opt) => rm::handle_rm_command(opt).await,
+        Commands::Cp(opt) => cp::handle_cp_command(opt).await,
+        Commands::Find(opt) => find::handle_find_command(opt).await,
+        Commands::Completion(opt) => completion::print_completion(opt.shell, &mut Cli::command()),
+    };
+
+    certexpiry::warn_expiring_certs(cli.cert_expiry_warn_days);
+
+    // 失败时按错误类型返回退出码，见 errors::EXIT_CODES_HELP
+    if let Err(err) = result {
+        printer::print_cli_error(&err);
+        std::process::exit(err.exit_code());
     }
 }
 
@@ -283,6 +342,24 @@ pub async fn main(_args: Vec<String>) {
     register_app().await;
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_global_config_dir() {
+        let cli =
+            Cli::try_parse_from(["rustfs-cli", "ls", "local/bucket", "-C", "/tmp/rc"]).unwrap();
+        assert_eq!(cli.config_dir.as_deref(), Some("/tmp/rc"));
+    }
+}
+
 // Copyright (c) 2015-2022 MinIO, Inc.
 //
 // This file is part of MinIO Object Storage stack
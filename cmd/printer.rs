@@ -0,0 +1,198 @@
+use serde::Serialize;
+use std::fs::OpenOptions;
+use std::io::{self, IsTerminal};
+use std::sync::Mutex;
+use tracing::Level;
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::fmt::{self, writer::BoxMakeWriter};
+use tracing_subscriber::prelude::*;
+
+use super::errors::CliError;
+use super::globals::{self, GlobalFlags};
+
+// 命令输出的消息：默认输出 to_text() 的文本，--json 时每条消息输出一行 JSON
+pub trait Message: Serialize {
+    fn to_text(&self) -> String;
+}
+
+pub fn print_msg<M: Message>(msg: &M) {
+    if !globals::global_flags().json {
+        println!("{}", msg.to_text());
+        return;
+    }
+    match serde_json::to_string(msg) {
+        Ok(line) => println!("{}", line),
+        Err(err) => print_error(&format!("Unable to marshal message: {}", err)),
+    }
+}
+
+// 输出 JSON 文档（例如 alias export 的结果），默认缩进便于阅读，--json 时输出为一行
+pub fn print_json<T: Serialize>(value: &T) {
+    let result = if globals::global_flags().json {
+        serde_json::to_string(value)
+    } else {
+        serde_json::to_string_pretty(value)
+    };
+    match result {
+        Ok(json) => println!("{}", json),
+        Err(err) => print_error(&format!("Unable to marshal message: {}", err)),
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorMessage<'a> {
+    status: &'static str,
+    error: ErrorDetail<'a>,
+}
+
+#[derive(Serialize)]
+struct ErrorDetail<'a> {
+    message: &'a str,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    kind: Option<&'static str>,
+    #[serde(rename = "exitCode", skip_serializing_if = "Option::is_none")]
+    exit_code: Option<i32>,
+}
+
+// 错误输出到 stderr，--json 时同样为一行 JSON
+pub fn print_error(msg: &str) {
+    if !globals::global_flags().json {
+        eprintln!("{}", msg);
+        return;
+    }
+    let msg = ErrorMessage {
+        status: "error",
+        error: ErrorDetail {
+            message: msg,
+            kind: None,
+            exit_code: None,
+        },
+    };
+    eprintln!("{}", serde_json::to_string(&msg).unwrap());
+}
+
+// 命令失败时输出，--json 时带上错误类型和退出码
+pub fn print_cli_error(err: &CliError) {
+    if !globals::global_flags().json {
+        eprintln!("{}", err);
+        return;
+    }
+    let msg = ErrorMessage {
+        status: "error",
+        error: ErrorDetail {
+            message: err.message(),
+            kind: Some(err.kind()),
+            exit_code: Some(err.exit_code()),
+        },
+    };
+    eprintln!("{}", serde_json::to_string(&msg).unwrap());
+}
+
+// --debug 时输出的日志：本程序的调试信息、HTTP 请求和响应以及 SDK 的重试。
+// 不包含 aws_sigv4，它的 TRACE 日志（签名前的请求、规范请求）中有 session token 和 SSE-C 密钥
+fn debug_targets() -> Targets {
+    Targets::new()
+        .with_target("rustfs_cli", Level::DEBUG)
+        .with_target("aws_smithy_runtime::client::orchestrator", Level::DEBUG)
+        // 解析 endpoint 时会输出凭证中的 access key
+        .with_target(
+            "aws_smithy_runtime::client::orchestrator::auth",
+            Level::INFO,
+        )
+        .with_target(
+            "aws_smithy_runtime::client::orchestrator::endpoints",
+            Level::INFO,
+        )
+        .with_target("aws_smithy_runtime::client::retries", Level::DEBUG)
+}
+
+// --debug 时安装 tracing subscriber，指定 --log-file 时追加到文件中
+pub fn init_debug_log(flags: &GlobalFlags) -> io::Result<()> {
+    if !flags.debug {
+        return Ok(());
+    }
+
+    let layer = match flags.log_file {
+        Some(ref path) => {
+            let file = OpenOptions::new().create(true).append(true).open(path)?;
+            fmt::layer()
+                .with_ansi(false)
+                .with_writer(BoxMakeWriter::new(Mutex::new(file)))
+        }
+        None => fmt::layer()
+            .with_ansi(!flags.no_color && io::stderr().is_terminal())
+            .with_writer(BoxMakeWriter::new(io::stderr)),
+    };
+    tracing_subscriber::registry()
+        .with(layer.with_filter(debug_targets()))
+        .init();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_credential_types::Credentials;
+    use aws_sigv4::http_request::{sign, SignableBody, SignableRequest, SigningSettings};
+    use aws_sigv4::sign::v4;
+    use std::sync::Arc;
+    use std::time::SystemTime;
+
+    #[derive(Clone)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_debug_log_hides_signing_secrets() {
+        let capture = Capture(Arc::new(Mutex::new(Vec::new())));
+        let writer = capture.clone();
+        let subscriber = tracing_subscriber::registry().with(
+            fmt::layer()
+                .with_ansi(false)
+                .with_writer(move || writer.clone())
+                .with_filter(debug_targets()),
+        );
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::debug!("signing probe");
+            let identity =
+                Credentials::new("ak", "sk", Some("TOKEN-VALUE".into()), None, "test").into();
+            let params = v4::SigningParams::builder()
+                .identity(&identity)
+                .region("us-east-1")
+                .name("s3")
+                .time(SystemTime::now())
+                .settings(SigningSettings::default())
+                .build()
+                .unwrap()
+                .into();
+            let request = SignableRequest::new(
+                "GET",
+                "https://s3.local/bucket/object",
+                [(
+                    "x-amz-server-side-encryption-customer-key",
+                    "SSE-C-KEY-VALUE",
+                )]
+                .into_iter(),
+                SignableBody::Bytes(&[]),
+            )
+            .unwrap();
+            sign(request, &params).unwrap();
+        });
+
+        let log = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        assert!(log.contains("signing probe"));
+        assert!(!log.contains("TOKEN-VALUE"));
+        assert!(!log.contains("SSE-C-KEY-VALUE"));
+    }
+}
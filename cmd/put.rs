@@ -6,9 +6,15 @@ use clap;
 use std::{path::Path, result::Result::Ok, sync::Arc};
 use tokio::{fs::File, sync::Mutex};
 
+use human_bytes::human_bytes;
 use indicatif::{ProgressBar, ProgressStyle};
+use serde::Serialize;
 use std::error::Error;
 use tokio::io::AsyncReadExt;
+
+use crate::cmd::errors::CliError;
+use crate::cmd::globals;
+use crate::cmd::printer::{self, Message};
 const CHUNK_SIZE: usize = 64 * 1024 * 1024; // 8 MB
 
 #[derive(clap::Args, Debug)]
@@ -36,53 +42,6 @@ pub struct PutOptions {
     )]
     pub enc_s3: Option<String>,
 
-    #[arg(
-        long = "config-dir",
-        short = 'C',
-        default_value = "/home/ldy/.mc",
-        help = "Path to configuration folder"
-    )]
-    pub config_dir: String,
-
-    #[arg(long = "quiet", short = 'q', help = "Disable progress bar display")]
-    pub quiet: bool,
-
-    #[arg(
-        long = "disable-pager",
-        help = "Disable mc internal pager and print to raw stdout"
-    )]
-    pub disable_pager: bool,
-
-    #[arg(long = "no-color", help = "Disable color theme")]
-    pub no_color: bool,
-
-    #[arg(long = "json", help = "Enable JSON lines formatted output")]
-    pub json: bool,
-
-    #[arg(long = "debug", help = "Enable debug output")]
-    pub debug: bool,
-
-    #[arg(
-        long = "resolve",
-        help = "Resolve HOST[:PORT] to an IP address. Example: minio.local:9000=10.10.75.1"
-    )]
-    pub resolve: Option<String>,
-
-    #[arg(long = "insecure", help = "Disable SSL certificate verification")]
-    pub insecure: bool,
-
-    #[arg(
-        long = "limit-upload",
-        help = "Limit upload rate in KiB/s, MiB/s, GiB/s"
-    )]
-    pub limit_upload: Option<String>,
-
-    #[arg(
-        long = "limit-download",
-        help = "Limit download rate in KiB/s, MiB/s, GiB/s"
-    )]
-    pub limit_download: Option<String>,
-
     #[arg(
         long = "checksum",
         help = "Add checksum to uploaded object. Values: MD5, CRC32, CRC32C, SHA1 or SHA256"
@@ -109,8 +68,30 @@ pub struct PutOptions {
     pub disable_multipart: bool,
 }
 
-pub async fn handle_put_command(opt: &PutOptions) -> Result<(), Box<dyn std::error::Error>> {
-    put(opt).await
+// 上传完成后输出的消息，cp 命令共用
+#[derive(Serialize)]
+pub struct CopyMessage {
+    pub status: String,
+    pub source: String,
+    pub target: String,
+    pub size: u64,
+}
+
+impl Message for CopyMessage {
+    fn to_text(&self) -> String {
+        format!(
+            "`{}` -> `{}` ({})",
+            self.source,
+            self.target,
+            human_bytes(self.size as f64)
+        )
+    }
+}
+
+pub async fn handle_put_command(opt: &PutOptions) -> Result<(), CliError> {
+    put(opt)
+        .await
+        .map_err(|err| err.context(format!("Unable to upload `{}`", opt.src)))
 }
 
 fn split_first_part(input: &str) -> (&str, &str) {
@@ -256,17 +237,23 @@ mod tests {
     }
 }
 
-pub async fn put(opt: &PutOptions) -> Result<(), Box<dyn std::error::Error>> {
+pub async fn put(opt: &PutOptions) -> Result<(), CliError> {
     if opt.src.is_empty() {
-        return Err("Path is empty".into());
+        return Err(CliError::InvalidArgument("path is empty".to_string()));
     }
-    let (alias, bucket, key) = generate_s3_key(&opt.src, &opt.target)?;
+    let (alias, bucket, key) =
+        generate_s3_key(&opt.src, &opt.target).map_err(CliError::InvalidArgument)?;
     let cli = crate::s3::client::get_s3client_from_alias(&alias)?;
-    println!("keys is {}", key);
 
     let mut file = File::open(&opt.src).await?;
     let file_size = file.metadata().await?.len();
-    let progress_bar = ProgressBar::new(file_size);
+    // -q 或 --json 时不显示进度条
+    let flags = globals::global_flags();
+    let progress_bar = if flags.quiet || flags.json {
+        ProgressBar::hidden()
+    } else {
+        ProgressBar::new(file_size)
+    };
     let progress_style = ProgressStyle::default_bar()
     .template("{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec}, {eta})")
     .expect("Failed to create progress style") // Unwrap or handle the error here
@@ -283,7 +270,7 @@ pub async fn put(opt: &PutOptions) -> Result<(), Box<dyn std::error::Error>> {
 
     let upload_id = create_resp
         .upload_id()
-        .expect("Failed to get upload ID")
+        .ok_or_else(|| CliError::Other("server did not return an upload ID".to_string()))?
         .to_string();
 
     let mut offset = 0;
@@ -310,8 +297,6 @@ pub async fn put(opt: &PutOptions) -> Result<(), Box<dyn std::error::Error>> {
             break;
         }
 
-        println!("size is {}", total_bytes_read);
-
         let cli = Arc::clone(&s3_client);
         let upload_id = upload_id.clone();
         let bucket = bucket.clone();
@@ -331,10 +316,12 @@ pub async fn put(opt: &PutOptions) -> Result<(), Box<dyn std::error::Error>> {
                 .part_number(current_part_number)
                 .body(ByteStream::from(buffer))
                 .send()
-                .await
-                .expect("Failed to upload part");
+                .await?;
 
-            let e_tag = part_resp.e_tag().expect("Failed to get ETag").to_string();
+            let e_tag = part_resp
+                .e_tag()
+                .ok_or_else(|| CliError::Other("server did not return an ETag".to_string()))?
+                .to_string();
             progress_bar.inc(total_bytes_read as u64);
 
             let mut completed_parts_lock = completed_parts.lock().await;
@@ -344,6 +331,7 @@ pub async fn put(opt: &PutOptions) -> Result<(), Box<dyn std::error::Error>> {
                     .set_e_tag(Some(e_tag))
                     .build(),
             );
+            Ok::<(), CliError>(())
         });
 
         handles.push(handle);
@@ -351,8 +339,26 @@ pub async fn put(opt: &PutOptions) -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Wait for all uploads to finish
+    // 任一分片上传失败时取消本次上传，避免服务端残留未完成的分片
+    let mut result = Ok(());
     for handle in handles {
-        handle.await?;
+        let ret = handle
+            .await
+            .map_err(|err| CliError::Other(err.to_string()))
+            .and_then(|ret| ret);
+        if result.is_ok() {
+            result = ret;
+        }
+    }
+    if let Err(err) = result {
+        let _ = s3_client
+            .abort_multipart_upload()
+            .bucket(&bucket)
+            .key(&key)
+            .upload_id(&upload_id)
+            .send()
+            .await;
+        return Err(err);
     }
 
     // Complete the multipart upload
@@ -365,13 +371,19 @@ pub async fn put(opt: &PutOptions) -> Result<(), Box<dyn std::error::Error>> {
 
     s3_client
         .complete_multipart_upload()
-        .bucket(bucket)
-        .key(key)
+        .bucket(&bucket)
+        .key(&key)
         .upload_id(upload_id)
         .multipart_upload(completed_upload)
         .send()
         .await?;
 
-    progress_bar.finish_with_message("Upload complete");
+    progress_bar.finish_and_clear();
+    printer::print_msg(&CopyMessage {
+        status: "success".to_string(),
+        source: opt.src.clone(),
+        target: format!("{}/{}/{}", alias, bucket, key),
+        size: file_size,
+    });
     Ok(())
 }
@@ -1,3 +1,8 @@
+use serde::Serialize;
+
+use crate::cmd::errors::CliError;
+use crate::cmd::printer::{self, Message};
+
 #[derive(clap::Args, Debug)]
 pub struct RbOptions {
     #[arg(help = "alias/bucket   (rustfs/bucketxyx)")]
@@ -12,69 +17,24 @@ pub struct RbOptions {
     /// Allow site-wide removal of objects
     #[clap(long, action, help = "Allow site-wide removal of objects")]
     pub dangerous: bool,
+}
 
-    /// Path to configuration folder (default: "/home/ldy/.mc")
-    #[clap(
-        long,
-        short = 'C',
-        default_value = "/home/ldy/.mc",
-        help = "Path to configuration folder"
-    )]
-    pub config_dir: String,
-
-    /// Disable progress bar display
-    #[clap(long, short = 'q', action, help = "Disable progress bar display")]
-    pub quiet: bool,
-
-    /// Disable mc internal pager and print to raw stdout
-    #[clap(
-        long = "disable-pager",
-        alias = "dp",
-        action,
-        help = "Disable mc internal pager and print to raw stdout"
-    )]
-    pub disable_pager: bool,
-
-    /// Disable color theme
-    #[clap(long = "no-color", action, help = "Disable color theme")]
-    pub no_color: bool,
-
-    /// Enable JSON lines formatted output
-    #[clap(long, action, help = "Enable JSON lines formatted output")]
-    pub json: bool,
-
-    /// Enable debug output
-    #[clap(long, action, help = "Enable debug output")]
-    pub debug: bool,
-
-    /// Resolve HOST[:PORT] to an IP address (Example: minio.local:9000=10.10.75.1)
-    #[clap(
-        long,
-        help = "Resolves HOST[:PORT] to an IP address. Example: minio.local:9000=10.10.75.1"
-    )]
-    pub resolve: Option<String>,
-
-    /// Disable SSL certificate verification
-    #[clap(long, action, help = "Disable SSL certificate verification")]
-    pub insecure: bool,
-
-    /// Limits uploads to a maximum rate in KiB/s, MiB/s, GiB/s (default: unlimited)
-    #[clap(
-        long = "limit-upload",
-        help = "Limits uploads to a maximum rate in KiB/s, MiB/s, GiB/s. (default: unlimited)"
-    )]
-    pub limit_upload: Option<String>,
+#[derive(Serialize)]
+struct RemoveBucketMessage {
+    status: String,
+    bucket: String,
+}
 
-    /// Limits downloads to a maximum rate in KiB/s, MiB/s, GiB/s (default: unlimited)
-    #[clap(
-        long = "limit-download",
-        help = "Limits downloads to a maximum rate in KiB/s, MiB/s, GiB/s. (default: unlimited)"
-    )]
-    pub limit_download: Option<String>,
+impl Message for RemoveBucketMessage {
+    fn to_text(&self) -> String {
+        format!("Removed `{}` successfully.", self.bucket)
+    }
 }
 
-pub async fn handle_rb_command(opt: &RbOptions) {
-    let _ = rb(opt).await;
+pub async fn handle_rb_command(opt: &RbOptions) -> Result<(), CliError> {
+    rb(opt)
+        .await
+        .map_err(|err| err.context(format!("Unable to remove bucket `{}`", opt.path)))
 }
 
 fn split_first_part(input: &str) -> (&str, &str) {
@@ -85,29 +45,22 @@ fn split_first_part(input: &str) -> (&str, &str) {
     (first_part, rest_part)
 }
 
-async fn rb(opt: &RbOptions) -> Result<(), Box<dyn std::error::Error>> {
+async fn rb(opt: &RbOptions) -> Result<(), CliError> {
     if opt.path.is_empty() {
-        println!("path is empty");
-        return Err("Path is empty".into());
+        return Err(CliError::InvalidArgument("path is empty".to_string()));
     }
     let (alias, key) = split_first_part(&opt.path);
     let (bucket, _) = split_first_part(key);
     if bucket == "" {
-        println!("bucket is null");
-        return Err("Bucket not provided".into());
+        return Err(CliError::InvalidArgument("bucket not provided".to_string()));
     }
 
     let cli = crate::s3::client::get_s3client_from_alias(alias)?;
 
-    //s3_client.create_bucket().bucket(bucket);
-    match cli.delete_bucket().bucket(bucket).send().await {
-        Ok(_) => {
-            println!("Bucket '{}' delete successfully", bucket);
-            Ok(())
-        }
-        Err(err) => {
-            println!("{}", err);
-            Err(Box::new(err))
-        }
-    }
+    cli.delete_bucket().bucket(bucket).send().await?;
+    printer::print_msg(&RemoveBucketMessage {
+        status: "success".to_string(),
+        bucket: opt.path.clone(),
+    });
+    Ok(())
 }
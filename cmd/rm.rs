@@ -3,6 +3,10 @@ use aws_sdk_s3::{
     types::{Delete, ObjectIdentifier},
 };
 use clap;
+use serde::Serialize;
+
+use crate::cmd::errors::CliError;
+use crate::cmd::printer::{self, Message};
 
 #[derive(clap::Args, Debug)]
 pub struct RmOptions {
@@ -60,53 +64,31 @@ pub struct RmOptions {
         help = "Remove object(s) versions that are non-current"
     )]
     pub non_current: bool,
+}
 
-    #[arg(
-        long = "config-dir",
-        short = 'C',
-        help = "Path to configuration folder"
-    )]
-    pub config_dir: Option<String>,
-
-    #[arg(short, long, help = "Disable progress bar display")]
-    pub quiet: bool,
-
-    #[arg(
-        long = "disable-pager",
-        help = "Disable internal pager and print to raw stdout"
-    )]
-    pub disable_pager: bool,
-
-    #[arg(long = "no-color", help = "Disable color theme")]
-    pub no_color: bool,
-
-    #[arg(long, help = "Enable JSON lines formatted output")]
-    pub json: bool,
-
-    #[arg(long, help = "Enable debug output")]
-    pub debug: bool,
-
-    #[arg(long, help = "Resolve HOST[:PORT] to an IP address")]
-    pub resolve: Option<String>,
-
-    #[arg(long, help = "Disable SSL certificate verification")]
-    pub insecure: bool,
+#[derive(Serialize)]
+struct RemoveMessage {
+    status: String,
+    key: String,
+}
 
-    #[arg(
-        long = "limit-upload",
-        help = "Limit upload rate (KiB/s, MiB/s, GiB/s)"
-    )]
-    pub limit_upload: Option<String>,
+impl Message for RemoveMessage {
+    fn to_text(&self) -> String {
+        format!("Removed `{}`.", self.key)
+    }
+}
 
-    #[arg(
-        long = "limit-download",
-        help = "Limit download rate (KiB/s, MiB/s, GiB/s)"
-    )]
-    pub limit_download: Option<String>,
+fn print_removed(alias: &str, bucket: &str, key: &str) {
+    printer::print_msg(&RemoveMessage {
+        status: "success".to_string(),
+        key: format!("{}/{}/{}", alias, bucket, key),
+    });
 }
 
-pub async fn handle_rm_command(opt: &RmOptions) -> Result<(), Box<dyn std::error::Error>> {
-    rm(opt).await
+pub async fn handle_rm_command(opt: &RmOptions) -> Result<(), CliError> {
+    rm(opt)
+        .await
+        .map_err(|err| err.context(format!("Unable to remove `{}`", opt.path)))
 }
 
 fn generate_s3_key(path: &str) -> Result<(String, String, String), String> {
@@ -149,12 +131,14 @@ fn generate_s3_key(path: &str) -> Result<(String, String, String), String> {
     Ok((alias, bucket, key))
 }
 
-pub async fn rm(opt: &RmOptions) -> Result<(), Box<dyn std::error::Error>> {
-    let (alias, bucket, key) = generate_s3_key(&opt.path)?;
+pub async fn rm(opt: &RmOptions) -> Result<(), CliError> {
+    let (alias, bucket, key) = generate_s3_key(&opt.path).map_err(CliError::InvalidArgument)?;
     let cli = crate::s3::client::get_s3client_from_alias(&alias)?;
 
     if opt.recursive {
         let mut continuation_token = None;
+        // 部分对象删除失败时继续删除其余对象，最后返回 PartialFailure
+        let mut failed = 0;
 
         loop {
             // List objects with the given prefix
@@ -181,22 +165,30 @@ pub async fn rm(opt: &RmOptions) -> Result<(), Box<dyn std::error::Error>> {
                     })
                     .collect();
 
-                let keys = match keys {
-                    Ok(keys) => keys,
-                    Err(err) => {
-                        eprintln!("Error building ObjectIdentifier: {}", err);
-                        return Err(Box::new(err)); // Return an error if building fails
-                    }
-                };
+                let keys = keys.map_err(|err| CliError::Other(err.to_string()))?;
                 // Create the Delete object
                 let delete = Delete::builder().set_objects(Some(keys)).build();
 
-                if delete.is_ok() {
-                    cli.delete_objects()
+                if let Ok(delete) = delete {
+                    let output = cli
+                        .delete_objects()
                         .bucket(&bucket)
-                        .delete(delete.unwrap())
+                        .delete(delete)
                         .send()
                         .await?;
+                    for deleted in output.deleted() {
+                        print_removed(&alias, &bucket, deleted.key().unwrap_or_default());
+                    }
+                    for err in output.errors() {
+                        printer::print_error(&format!(
+                            "Failed to remove `{}/{}/{}`: {}",
+                            alias,
+                            bucket,
+                            err.key().unwrap_or_default(),
+                            err.message().unwrap_or_default()
+                        ));
+                        failed += 1;
+                    }
                 }
             }
 
@@ -208,20 +200,16 @@ pub async fn rm(opt: &RmOptions) -> Result<(), Box<dyn std::error::Error>> {
             }
         }
 
+        if failed > 0 {
+            return Err(CliError::PartialFailure(format!(
+                "{} object(s) could not be removed",
+                failed
+            )));
+        }
         Ok(())
     } else {
-        match cli.delete_object().bucket(&bucket).key(&key).send().await {
-            Ok(_) => {
-                println!(
-                    "Object '{}' in bucket '{}' deleted successfully",
-                    &key, &bucket
-                );
-                Ok(())
-            }
-            Err(err) => {
-                println!("Error deleting object: {}", err);
-                Err(Box::new(err))
-            }
-        }
+        cli.delete_object().bucket(&bucket).key(&key).send().await?;
+        print_removed(&alias, &bucket, &key);
+        Ok(())
     }
 }
@@ -32,54 +32,6 @@ pub struct StatOptions {
         help = "encrypt/decrypt objects using client provided keys (multiple keys can be provided)"
     )]
     pub enc_c: Vec<String>,
-
-    #[arg(
-        long = "config-dir",
-        short = 'C',
-        help = "path to configuration folder",
-        default_value = "/home/ldy/.mc"
-    )]
-    pub config_dir: String,
-
-    #[arg(short, long, help = "disable progress bar display")]
-    pub quiet: bool,
-
-    #[arg(
-        long = "disable-pager",
-        alias = "dp",
-        help = "disable mc internal pager and print to raw stdout"
-    )]
-    pub disable_pager: bool,
-
-    #[arg(long, help = "disable color theme")]
-    pub no_color: bool,
-
-    #[arg(long, help = "enable JSON lines formatted output")]
-    pub json: bool,
-
-    #[arg(long, help = "enable debug output")]
-    pub debug: bool,
-
-    #[arg(
-        long,
-        help = "resolves HOST[:PORT] to an IP address (e.g., minio.local:9000=10.10.75.1)"
-    )]
-    pub resolve: Option<String>,
-
-    #[arg(long, help = "disable SSL certificate verification")]
-    pub insecure: bool,
-
-    #[arg(
-        long = "limit-upload",
-        help = "limits uploads to a maximum rate in KiB/s, MiB/s, GiB/s (default: unlimited)"
-    )]
-    pub limit_upload: Option<String>,
-
-    #[arg(
-        long = "limit-download",
-        help = "limits downloads to a maximum rate in KiB/s, MiB/s, GiB/s (default: unlimited)"
-    )]
-    pub limit_download: Option<String>,
 }
 
 pub async fn handle_stat_command(opt: &StatOptions) {
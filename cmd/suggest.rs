@@ -0,0 +1,104 @@
+use clap::Command;
+
+// 允许的最大输入错误数（漏输、多输、输错以及相邻字符颠倒），与 mc 相同
+const MAX_TYPOS: usize = 1;
+
+// 两个字符串的 Damerau-Levenshtein 距离（相邻字符交换计为一次编辑）
+pub fn damerau_levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+// 以 name 为前缀或者只差一次输入错误的候选项，已排序
+pub fn find_closest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut closest: Vec<String> = candidates
+        .into_iter()
+        .filter(|candidate| {
+            candidate.starts_with(name) || damerau_levenshtein(name, candidate) <= MAX_TYPOS
+        })
+        .map(String::from)
+        .collect();
+    closest.sort();
+    closest.dedup();
+    closest
+}
+
+// 追加在错误信息后的提示，没有相近的候选项时为空
+pub fn did_you_mean(closest: &[String]) -> String {
+    if closest.is_empty() {
+        return String::new();
+    }
+    let lines: Vec<String> = closest.iter().map(|c| format!("        `{}`", c)).collect();
+    format!("\n\nDid you mean one of these?\n{}", lines.join("\n"))
+}
+
+// 子命令不存在时的错误信息，args 为完整的命令行参数，用来找到出错的那一级命令
+pub fn command_not_found(cmd: &mut Command, args: &[String], name: &str) -> String {
+    cmd.build();
+    let mut parent = &*cmd;
+    for arg in args.iter().skip(1) {
+        if arg == name {
+            break;
+        }
+        if let Some(sub) = parent.find_subcommand(arg) {
+            parent = sub;
+        }
+    }
+
+    let names = parent
+        .get_subcommands()
+        .filter(|sub| !sub.is_hide_set())
+        .map(Command::get_name);
+    format!(
+        "`{}` is not a recognized command. Get help using `--help` flag.{}",
+        name,
+        did_you_mean(&find_closest(name, names))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_damerau_levenshtein() {
+        assert_eq!(damerau_levenshtein("ls", "ls"), 0);
+        assert_eq!(damerau_levenshtein("lss", "ls"), 1);
+        assert_eq!(damerau_levenshtein("sl", "ls"), 1);
+        assert_eq!(damerau_levenshtein("mb", "rb"), 1);
+        assert_eq!(damerau_levenshtein("", "put"), 3);
+        assert_eq!(damerau_levenshtein("fnid", "find"), 1);
+        assert_eq!(damerau_levenshtein("ca", "abc"), 3);
+    }
+
+    #[test]
+    fn test_find_closest() {
+        let commands = [
+            "alias", "admin", "ls", "mb", "rb", "put", "rm", "cp", "find",
+        ];
+        assert_eq!(find_closest("sl", commands), ["ls"]);
+        assert_eq!(find_closest("a", commands), ["admin", "alias"]);
+        assert_eq!(find_closest("xb", commands), ["mb", "rb"]);
+        assert!(find_closest("upload", commands).is_empty());
+    }
+}
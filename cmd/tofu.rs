@@ -1,8 +1,29 @@
-use aws_sdk_s3::config::Region;
-use aws_sdk_s3::{Client, Config};
+use aws_sdk_s3::error::{ProvideErrorMetadata, SdkError};
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use lazy_static::lazy_static;
 use rand::{distributions::Alphanumeric, Rng};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::WebPkiServerVerifier;
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{DigitallySignedStruct, RootCertStore, SignatureScheme};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::error::Error;
-use tokio;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use super::certexpiry;
+use super::config;
+use super::configx::{self, AliasConfigV10};
+use crate::s3::http::endpoint_key;
+
+lazy_static! {
+    // 握手时遇到的不受信任的服务端证书（DER），按 host:port 记录，供 `alias set` 确认后固定
+    static ref UNTRUSTED_CERTS: Mutex<HashMap<String, Vec<u8>>> = Mutex::new(HashMap::new());
+}
 
 // 生成随机 bucket 名字的函数
 fn generate_random_bucket_name() -> String {
@@ -16,48 +37,425 @@ fn generate_random_bucket_name() -> String {
     format!("probe-bucket-{}", random_string)
 }
 
-// 检查随机 bucket 的权限
-pub async fn check_bucket_permissions(
-    url: &str,
-    ak: &str,
-    sk: &str,
-    region: &str,
-) -> Result<bool, Box<dyn Error>> {
+// 校验别名凭证失败的原因
+#[derive(Debug)]
+pub enum CredentialError {
+    // 服务端不认识该 Access Key
+    InvalidAccessKey,
+    // Secret Key 错误导致签名不匹配
+    BadSignature,
+    // 本地时间与服务端相差过大
+    ClockSkew,
+    // 无法连接服务端（DNS、连接被拒绝、超时等）
+    Unreachable(String),
+    // TLS 握手或证书校验失败
+    Tls(String),
+    // 创建客户端失败或服务端返回了其他错误
+    Other(String),
+}
+
+impl std::fmt::Display for CredentialError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CredentialError::InvalidAccessKey => {
+                write!(f, "the access key does not exist on the server")
+            }
+            CredentialError::BadSignature => write!(
+                f,
+                "the request signature does not match, check the secret key"
+            ),
+            CredentialError::ClockSkew => write!(
+                f,
+                "the local clock differs too much from the server time, sync the system clock"
+            ),
+            CredentialError::Unreachable(msg) => write!(f, "unable to reach the server: {}", msg),
+            CredentialError::Tls(msg) => write!(f, "TLS error: {}", msg),
+            CredentialError::Other(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl Error for CredentialError {}
+
+// 证书的 SHA-256 指纹，格式与 `openssl x509 -fingerprint -sha256` 相同
+pub fn cert_fingerprint(der: &[u8]) -> String {
+    Sha256::digest(der)
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+// 固定的证书指纹保存在 <config-dir>/certs/<host>_<port>.sha256
+fn pin_path(endpoint: &str) -> io::Result<PathBuf> {
+    let file_name: String = endpoint
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    Ok(PathBuf::from(config::get_mc_config_dir()?)
+        .join("certs")
+        .join(format!("{}.sha256", file_name)))
+}
+
+fn load_pin(endpoint: &str) -> Option<String> {
+    let pin = fs::read_to_string(pin_path(endpoint).ok()?).ok()?;
+    Some(pin.trim().to_string())
+}
+
+fn save_pin(endpoint: &str, fingerprint: &str) -> io::Result<()> {
+    let path = pin_path(endpoint)?;
+    if let Some(dir) = path.parent() {
+        configx::create_private_dir(&dir.to_string_lossy())?;
+    }
+    configx::write_private_file_atomic(
+        &path.to_string_lossy(),
+        format!("{}\n", fingerprint).as_bytes(),
+    )
+}
+
+// 证书校验器：已固定指纹的服务端只比较指纹（允许自签名证书），
+// 其余服务端按根证书正常校验，校验失败时记录证书以便用户确认。
+// insecure 时不校验证书，但仍校验握手签名
+#[derive(Debug)]
+pub struct PinningVerifier {
+    endpoint: String,
+    inner: Arc<WebPkiServerVerifier>,
+    provider: Arc<CryptoProvider>,
+    insecure: bool,
+}
+
+impl PinningVerifier {
+    pub fn new(
+        endpoint: String,
+        roots: RootCertStore,
+        provider: Arc<CryptoProvider>,
+        insecure: bool,
+    ) -> Result<Self, Box<dyn Error>> {
+        let inner = WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider.clone())
+            .build()?;
+        Ok(PinningVerifier {
+            endpoint,
+            inner,
+            provider,
+            insecure,
+        })
+    }
+}
+
+impl PinningVerifier {
+    fn verify_end_entity(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        if self.insecure {
+            return Ok(ServerCertVerified::assertion());
+        }
+        if let Some(pin) = load_pin(&self.endpoint) {
+            let fingerprint = cert_fingerprint(end_entity);
+            if pin.eq_ignore_ascii_case(&fingerprint) {
+                return Ok(ServerCertVerified::assertion());
+            }
+            return Err(rustls::Error::General(format!(
+                "certificate of {} changed: pinned SHA-256 {}, server presented {}",
+                self.endpoint, pin, fingerprint
+            )));
+        }
+
+        self.inner
+            .verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+            .inspect_err(|_| {
+                UNTRUSTED_CERTS
+                    .lock()
+                    .unwrap()
+                    .insert(self.endpoint.clone(), end_entity.to_vec());
+            })
+    }
+}
+
+impl ServerCertVerifier for PinningVerifier {
+    // 校验通过后记录证书的过期时间，命令结束时提示即将过期的证书
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let verified =
+            self.verify_end_entity(end_entity, intermediates, server_name, ocsp_response, now)?;
+        certexpiry::record_peer_cert(&self.endpoint, end_entity);
+        Ok(verified)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+// 显示证书信息并询问是否信任，非交互环境下不信任
+fn confirm_certificate(endpoint: &str, der: &[u8], fingerprint: &str) -> bool {
+    eprintln!("The certificate of {} is not trusted.", endpoint);
+    if let Ok((_, cert)) = x509_parser::parse_x509_certificate(der) {
+        eprintln!("  Subject:    {}", cert.subject());
+        eprintln!("  Issuer:     {}", cert.issuer());
+        eprintln!("  Not before: {}", cert.validity().not_before);
+        eprintln!("  Not after:  {}", cert.validity().not_after);
+    }
+    eprintln!("  SHA-256:    {}", fingerprint);
+
+    if !io::stdin().is_terminal() {
+        return false;
+    }
+    eprint!("Trust this certificate and pin it for future connections? [y/N]: ");
+    let _ = io::stderr().flush();
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim(), "y" | "Y" | "yes" | "Yes")
+}
+
+// 拼接错误及其 source 链的描述
+pub fn error_chain(err: &(dyn Error + 'static)) -> String {
+    let mut msgs = vec![err.to_string()];
+    let mut source = err.source();
+    while let Some(err) = source {
+        let msg = err.to_string();
+        if !msgs.iter().any(|m| m.contains(&msg)) {
+            msgs.push(msg);
+        }
+        source = err.source();
+    }
+    msgs.join(": ")
+}
+
+// 判断错误链中是否包含 rustls 的错误（io::Error 包裹的内部错误不会出现在 source 链中）
+pub fn is_tls_error(err: &(dyn Error + 'static)) -> bool {
+    let mut current = Some(err);
+    while let Some(err) = current {
+        if err.is::<rustls::Error>() {
+            return true;
+        }
+        if let Some(inner) = err
+            .downcast_ref::<std::io::Error>()
+            .and_then(|io_err| io_err.get_ref())
+        {
+            if is_tls_error(inner) {
+                return true;
+            }
+        }
+        current = err.source();
+    }
+    false
+}
+
+// 命令输出用的 SDK 错误描述：服务端错误取错误码和消息，其他错误取完整的错误链
+pub fn describe_sdk_error<E>(err: &SdkError<E, HttpResponse>) -> String
+where
+    E: ProvideErrorMetadata + Error + 'static,
+{
+    match err {
+        SdkError::ServiceError(service_err) => match service_err.err().code() {
+            Some(code) => format!("{} ({})", service_err.err().message().unwrap_or(code), code),
+            None => format!(
+                "server returned status {}",
+                service_err.raw().status().as_u16()
+            ),
+        },
+        err => error_chain(err),
+    }
+}
+
+// 将 SDK 返回的错误归类，服务端拒绝访问随机 bucket 说明凭证本身有效
+fn classify_error<E>(err: SdkError<E, HttpResponse>) -> Result<(), CredentialError>
+where
+    E: ProvideErrorMetadata + Error + 'static,
+{
+    match err {
+        SdkError::ServiceError(service_err) => {
+            let status = service_err.raw().status().as_u16();
+            match service_err.err().code() {
+                // 只有签名校验通过后服务端才会判断 bucket 是否存在以及是否有权限访问，
+                // 所以 AccessDenied 同样说明 Access Key 和 Secret Key 有效，
+                // 只是该用户没有权限访问这个随机 bucket（例如只授权了部分 bucket 的用户）
+                Some("NoSuchBucket") | Some("AccessDenied") => Ok(()),
+                Some("InvalidAccessKeyId") => Err(CredentialError::InvalidAccessKey),
+                Some("SignatureDoesNotMatch") => Err(CredentialError::BadSignature),
+                Some("RequestTimeTooSkewed") => Err(CredentialError::ClockSkew),
+                None if status == 404 => Ok(()),
+                // 407 来自代理而不是 S3 服务端，响应体中的错误码没有意义
+                _ if status == 407 => Err(CredentialError::Other(
+                    "proxy authentication required".to_string(),
+                )),
+                code => Err(CredentialError::Other(format!(
+                    "server returned {} ({})",
+                    code.unwrap_or("an error"),
+                    service_err.err().message().unwrap_or("no message")
+                ))),
+            }
+        }
+        SdkError::DispatchFailure(ref failure) => {
+            if is_tls_error(&err) {
+                Err(CredentialError::Tls(error_chain(&err)))
+            } else if failure.is_io() || failure.is_timeout() {
+                Err(CredentialError::Unreachable(error_chain(&err)))
+            } else {
+                Err(CredentialError::Other(error_chain(&err)))
+            }
+        }
+        SdkError::TimeoutError(_) => Err(CredentialError::Unreachable(
+            "request timed out".to_string(),
+        )),
+        err => Err(CredentialError::Other(error_chain(&err))),
+    }
+}
+
+// 访问一个随机 bucket 来校验别名的地址和凭证
+async fn check_credentials(alias_cfg: &AliasConfigV10) -> Result<(), CredentialError> {
     // 创建 AWS 配置和 S3 客户端
-    println!("Generated random bucket name:{}", url);
-    let s3_client = crate::s3::client::get_s3client_from_para(ak, sk, url, region)?;
+    let s3_client = crate::s3::client::get_s3client_from_config(alias_cfg)
+        .map_err(|err| CredentialError::Other(err.to_string()))?;
 
     // 生成一个随机的 bucket 名字
     let random_bucket_name = generate_random_bucket_name();
-    let head_bucket_result = s3_client
+    match s3_client
         .get_bucket_location()
         .bucket(&random_bucket_name)
         .send()
-        .await;
-    match head_bucket_result {
-        Ok(_) => {
-            println!("Bucket '{}' exists and is accessible.", random_bucket_name);
-            Ok(true)
-        }
-        Err(err) => {
-            let err_msg = format!("{:?}", err);
-            //println!("err: {}\n", err_msg);
-            if err_msg.contains("NoSuchBucket") {
-                println!(
-                    "Bucket '{}' does not exist, access key and secret key are valid.",
-                    random_bucket_name
-                );
-                Ok(true)
-            } else if err_msg.contains("SignatureDoesNotMatch") {
-                println!("SignatureDoesNotMatch '{}'.", random_bucket_name);
-                Ok(false)
-            } else if err_msg.contains("InvalidAccessKeyId") {
-                println!("InvalidAccessKeyId '{}': {:?}", random_bucket_name, err);
-                Ok(false)
-            } else {
-                println!("other err {}", err);
-                Ok(false)
-            }
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(err) => classify_error(err),
+    }
+}
+
+// 校验别名凭证；首次连接使用不受信任证书的 HTTPS 服务端时，
+// 经用户确认（或 trust_cert 为 true）后固定其证书指纹并重试
+pub async fn check_bucket_permissions(
+    alias_cfg: &AliasConfigV10,
+    trust_cert: bool,
+) -> Result<(), CredentialError> {
+    let err = match check_credentials(alias_cfg).await {
+        Err(CredentialError::Tls(err)) => err,
+        ret => return ret,
+    };
+    let endpoint = endpoint_key(&alias_cfg.url).unwrap_or_default();
+    let Some(der) = UNTRUSTED_CERTS.lock().unwrap().remove(&endpoint) else {
+        return Err(CredentialError::Tls(err));
+    };
+
+    let fingerprint = cert_fingerprint(&der);
+    if !trust_cert && !confirm_certificate(&endpoint, &der, &fingerprint) {
+        return Err(CredentialError::Tls(format!(
+            "{}; certificate {} was not trusted, confirm it interactively or pass --trust-cert",
+            err, fingerprint
+        )));
+    }
+    save_pin(&endpoint, &fingerprint).map_err(|err| {
+        CredentialError::Other(format!(
+            "unable to save the certificate fingerprint: {}",
+            err
+        ))
+    })?;
+    eprintln!("Pinned certificate {} for {}.", fingerprint, endpoint);
+    check_credentials(alias_cfg).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_s3::operation::get_bucket_location::GetBucketLocationError;
+    use aws_smithy_runtime_api::http::StatusCode;
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::error::ErrorMetadata;
+
+    // 构造服务端返回指定错误码（None 表示响应中没有错误码）和状态码的错误
+    fn service_error(
+        code: Option<&str>,
+        status: u16,
+    ) -> SdkError<GetBucketLocationError, HttpResponse> {
+        let mut meta = ErrorMetadata::builder().message("test");
+        if let Some(code) = code {
+            meta = meta.code(code);
+        }
+        let raw = HttpResponse::new(StatusCode::try_from(status).unwrap(), SdkBody::empty());
+        SdkError::service_error(GetBucketLocationError::generic(meta.build()), raw)
+    }
+
+    #[test]
+    fn test_classify_error() {
+        assert!(classify_error(service_error(Some("NoSuchBucket"), 404)).is_ok());
+        // 凭证有效但没有访问该 bucket 的权限
+        assert!(classify_error(service_error(Some("AccessDenied"), 403)).is_ok());
+        // HEAD 等没有响应体的请求只有状态码
+        assert!(classify_error(service_error(None, 404)).is_ok());
+
+        assert!(matches!(
+            classify_error(service_error(Some("InvalidAccessKeyId"), 403)),
+            Err(CredentialError::InvalidAccessKey)
+        ));
+        assert!(matches!(
+            classify_error(service_error(Some("SignatureDoesNotMatch"), 403)),
+            Err(CredentialError::BadSignature)
+        ));
+        assert!(matches!(
+            classify_error(service_error(Some("RequestTimeTooSkewed"), 403)),
+            Err(CredentialError::ClockSkew)
+        ));
+        for code in [None, Some("ProxyAuthenticationRequired")] {
+            assert!(matches!(
+                classify_error(service_error(code, 407)),
+                Err(CredentialError::Other(msg)) if msg == "proxy authentication required"
+            ));
         }
+        assert!(matches!(
+            classify_error(service_error(Some("InternalError"), 500)),
+            Err(CredentialError::Other(msg)) if msg == "server returned InternalError (test)"
+        ));
+        assert!(matches!(
+            classify_error(service_error(None, 403)),
+            Err(CredentialError::Other(_))
+        ));
     }
 }
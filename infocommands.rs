@@ -188,6 +188,10 @@ pub struct DiskMetrics {
 }
 
 use crate::clientadmin;
+use crate::cmd::configx::AliasConfigV10;
+use crate::cmd::errors::CliError;
+use crate::cmd::printer::{self, Message};
+use crate::s3::{http, httptrace};
 
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct HealingDisk {
@@ -581,37 +585,36 @@ fn display_info(info: &InfoMessage) {
     // println!("{} drive online, {} drives offline, EC:{}", info.backend.onlineDisks, info.backend.offlineDisks, 0);
 }
 
-pub async fn ServerInfo() {
-    let res = clientadmin::get_request(
+pub async fn ServerInfo() -> Result<(), CliError> {
+    let url = "http://127.0.0.1:9000/minio/admin/v3/info?metrics=false".to_string();
+    let alias_cfg = AliasConfigV10 {
+        url: url.clone(),
+        ..Default::default()
+    };
+    let client = http::build_reqwest_client(&alias_cfg)
+        .map_err(|e| CliError::from(e).context("Unable to initialize admin client"))?;
+    let response = clientadmin::get_request(
+        &client,
         "12345678".to_string(),
         "12345678".to_string(),
-        "http://127.0.0.1:9000/minio/admin/v3/info?metrics=false".to_string(),
+        None,
+        url,
         "us-east-1".to_string(),
+        |request| httptrace::execute(&client, request),
     )
-    .await;
-    println!("\n");
-    match res {
-        Ok(response) => {
-            let ret: Result<InfoMessage, serde_json::Error> = serde_json::from_str(&response);
-
-            match ret {
-                Ok(msg) => {
-                    //println!("{}", response);
-                    let x = ClusterStruct {
-                        only_offline: false,
-                        info: Some(msg),
-                        status: "online".to_string(),
-                        error: None,
-                    };
-                    println!("{}", x);
-                }
-                Err(e) => {
-                    println!("json err: {}", e);
-                }
-            }
-        }
-        Err(e) => eprintln!("Error: {}", e),
-    }
+    .await
+    .map_err(|e| CliError::from(e).context("Unable to get server info"))?;
+    let msg: InfoMessage = serde_json::from_str(&response)
+        .map_err(|e| CliError::Other(format!("Unable to parse server info: {}", e)))?;
+
+    let x = ClusterStruct {
+        only_offline: false,
+        info: Some(msg),
+        status: "online".to_string(),
+        error: None,
+    };
+    printer::print_msg(&x);
+    Ok(())
 }
 #[derive(Deserialize, Serialize, Debug, Default, Clone)]
 pub struct MemStats {
@@ -645,14 +648,22 @@ fn endpoint_to_pools(endpoint: &str, c: &ClusterInfo) -> Vec<i32> {
     pools
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct ClusterStruct {
     status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     error: Option<String>,
     info: Option<InfoMessage>,
+    #[serde(skip)]
     only_offline: bool,
 }
 
+impl Message for ClusterStruct {
+    fn to_text(&self) -> String {
+        self.to_string()
+    }
+}
+
 impl fmt::Display for ClusterStruct {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // 检查集群状态中的错误
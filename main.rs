@@ -1,24 +1,35 @@
 mod cmd {
     pub mod admin;
     pub mod alias;
+    pub mod aliasedit;
     pub mod aliasexport;
+    pub mod aliasimport;
     pub mod aliaslist;
     pub mod aliasremove;
+    pub mod aliasrename;
     pub mod aliasset;
+    pub mod awsprofile;
+    pub mod certexpiry;
     //pub mod clientadmin;
     pub mod cmd;
+    pub mod completion;
     pub mod config;
     pub mod configx;
     pub mod cp;
+    pub mod errors;
     pub mod find;
+    pub mod globals;
     pub mod ls;
     pub mod lsmain;
     pub mod main;
     pub mod mb;
+    pub mod printer;
     pub mod put;
     pub mod rb;
     pub mod rm;
+    pub mod secretstore;
     pub mod stat;
+    pub mod suggest;
     pub mod tofu;
 }
 
@@ -1,34 +1,106 @@
+use super::{credential, http};
 use crate::cmd::aliasremove::get_alias;
+use crate::cmd::configx::AliasConfigV10;
 use aws_sdk_s3::config::BehaviorVersion;
 use aws_sdk_s3::config::Credentials;
 use aws_sdk_s3::config::Region;
 use aws_sdk_s3::{Client as S3Client, Config};
+use chrono::Utc;
 use std::error::Error;
+use std::time::SystemTime;
+
+// 临时凭证距离过期不足该分钟数时给出提示
+const SESSION_EXPIRY_WARN_MINUTES: i64 = 15;
+// 别名未指定 region 时使用的默认值
+pub const DEFAULT_REGION: &str = "us-east-1";
 
 pub fn get_s3client_from_alias(alias: &str) -> Result<S3Client, Box<dyn Error>> {
     let conf = get_alias(alias)?; // Propagate error from get_alias directly
-    println!("get config {} suc", alias);
-    get_s3client_from_para(&conf.access_key, &conf.secret_key, &conf.url, "us-east-1")
+    tracing::debug!("loaded config for alias `{}`", alias);
+    warn_if_session_expiring(alias, &conf);
+    get_s3client_from_config(&conf)
 }
 
-pub fn get_s3client_from_para(
-    ak: &str,
-    sk: &str,
-    url: &str,
-    _region: &str,
-) -> Result<S3Client, Box<dyn Error>> {
-    let credentials = Credentials::new(ak, sk, None, None, "");
-    let region = Region::new("us-east-1".to_string());
+pub fn get_s3client_from_config(conf: &AliasConfigV10) -> Result<S3Client, Box<dyn Error>> {
+    let region = Region::new(conf.region.as_deref().unwrap_or(DEFAULT_REGION).to_string());
 
-    let config = Config::builder()
+    let mut builder = Config::builder()
         .region(region)
-        .endpoint_url(url.to_string())
-        .credentials_provider(credentials)
+        .endpoint_url(conf.url.clone())
+        .force_path_style(is_path_style(&conf.path, &conf.url));
+    builder = match conf.credential_process {
+        Some(ref command) => {
+            builder.credentials_provider(credential::ProcessCredentialsProvider::new(command))
+        }
+        None => builder.credentials_provider(get_credentials(
+            &conf.access_key,
+            &conf.secret_key,
+            conf.session_token.as_deref(),
+            conf.session_expiry.map(SystemTime::from),
+        )),
+    };
+    let config = builder
+        .http_client(http::build_http_client(conf)?)
         .behavior_version(BehaviorVersion::latest()) // Adjust as necessary
         .build();
     Ok(S3Client::from_conf(config))
 }
 
+// 根据别名的 path 设置决定是否使用 path-style 寻址：
+// "on" 总是 path-style，"off"/"dns" 总是 virtual-host，
+// "auto"（或为空）时仅对 AWS S3 域名使用 virtual-host
+pub fn is_path_style(path: &str, url: &str) -> bool {
+    match path {
+        "on" => true,
+        "off" | "dns" => false,
+        _ => {
+            let host = url::Url::parse(url)
+                .ok()
+                .and_then(|u| u.host_str().map(str::to_string))
+                .unwrap_or_default();
+            !(host == "amazonaws.com" || host.ends_with(".amazonaws.com"))
+        }
+    }
+}
+
+// 临时凭证已过期或即将过期时在 stderr 输出警告
+fn warn_if_session_expiring(alias: &str, conf: &AliasConfigV10) {
+    // credential_process 的凭证由 SDK 在过期前重新获取
+    if conf.credential_process.is_some() {
+        return;
+    }
+    let Some(expiry) = conf.session_expiry else {
+        return;
+    };
+    let remaining = expiry - Utc::now();
+    if remaining <= chrono::Duration::zero() {
+        eprintln!(
+            "WARN: session credentials for `{}` expired at {}. Refresh them with `alias set --session-token`.",
+            alias,
+            expiry.to_rfc3339()
+        );
+    } else if remaining <= chrono::Duration::minutes(SESSION_EXPIRY_WARN_MINUTES) {
+        eprintln!(
+            "WARN: session credentials for `{}` expire in {} minute(s), at {}.",
+            alias,
+            remaining.num_minutes().max(1),
+            expiry.to_rfc3339()
+        );
+    }
+}
+
+fn get_credentials(
+    ak: &str,
+    sk: &str,
+    session_token: Option<&str>,
+    expiry: Option<SystemTime>,
+) -> Credentials {
+    let session_token = session_token
+        .filter(|token| !token.is_empty())
+        .map(str::to_string);
+    Credentials::new(ak, sk, session_token, expiry, "rustfs-cli")
+}
+
 // pub fn check_bucket_permissions(
 //     url: &str,
 //     access_key: &str,
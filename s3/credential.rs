@@ -0,0 +1,178 @@
+use aws_credential_types::provider::{error::CredentialsError, future, ProvideCredentials};
+use aws_sdk_s3::config::Credentials;
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::error::Error;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+// 距离过期不足该秒数时重新执行 credential_process
+const CREDENTIAL_REFRESH_SECONDS: i64 = 60;
+
+// credential_process 输出的 JSON，与 AWS CLI 的格式一致
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ProcessCredentials {
+    #[serde(default)]
+    pub version: Option<u32>,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    #[serde(default)]
+    pub session_token: Option<String>,
+    #[serde(default)]
+    pub expiration: Option<DateTime<Utc>>,
+}
+
+lazy_static! {
+    // 按命令缓存凭证直到过期，凭证只保存在内存中
+    static ref CREDENTIAL_CACHE: Mutex<HashMap<String, ProcessCredentials>> =
+        Mutex::new(HashMap::new());
+}
+
+impl ProcessCredentials {
+    // 没有 Expiration 的凭证在本次运行期间一直有效
+    fn is_fresh(&self) -> bool {
+        self.expiration.is_none_or(|expiration| {
+            expiration - Utc::now() > chrono::Duration::seconds(CREDENTIAL_REFRESH_SECONDS)
+        })
+    }
+}
+
+// 通过 credential_process 获取凭证的提供者，SDK 的凭证缓存在凭证过期前会再次调用它
+#[derive(Debug)]
+pub struct ProcessCredentialsProvider {
+    command: String,
+}
+
+impl ProcessCredentialsProvider {
+    pub fn new(command: &str) -> Self {
+        ProcessCredentialsProvider {
+            command: command.to_string(),
+        }
+    }
+
+    async fn credentials(&self) -> Result<Credentials, CredentialsError> {
+        let command = self.command.clone();
+        // 凭证工具可能需要较长时间（例如提示用户登录），不阻塞异步运行时
+        let credentials = tokio::task::spawn_blocking(move || {
+            resolve_credential_process(&command).map_err(|err| err.to_string())
+        })
+        .await
+        .map_err(CredentialsError::unhandled)?
+        .map_err(CredentialsError::provider_error)?;
+
+        Ok(Credentials::new(
+            credentials.access_key_id,
+            credentials.secret_access_key,
+            credentials.session_token.filter(|token| !token.is_empty()),
+            credentials.expiration.map(SystemTime::from),
+            "credential_process",
+        ))
+    }
+}
+
+impl ProvideCredentials for ProcessCredentialsProvider {
+    fn provide_credentials<'a>(&'a self) -> future::ProvideCredentials<'a>
+    where
+        Self: 'a,
+    {
+        future::ProvideCredentials::new(self.credentials())
+    }
+}
+
+// 执行别名配置的 credential_process 获取凭证，命中缓存时不再执行
+pub fn resolve_credential_process(command: &str) -> Result<ProcessCredentials, Box<dyn Error>> {
+    let mut cache = CREDENTIAL_CACHE.lock().unwrap();
+    if let Some(credentials) = cache.get(command).filter(|c| c.is_fresh()) {
+        return Ok(credentials.clone());
+    }
+
+    let credentials = run_credential_process(command)?;
+    cache.insert(command.to_string(), credentials.clone());
+    Ok(credentials)
+}
+
+fn run_credential_process(command: &str) -> Result<ProcessCredentials, Box<dyn Error>> {
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C");
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c");
+        cmd
+    };
+    // stderr 直接输出到终端，便于凭证工具提示用户登录
+    let output = cmd
+        .arg(command)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|err| format!("unable to run credential_process `{}`: {}", command, err))?;
+    if !output.status.success() {
+        return Err(format!(
+            "credential_process `{}` failed with {}",
+            command, output.status
+        )
+        .into());
+    }
+
+    parse_process_output(&output.stdout).map_err(|err| {
+        format!(
+            "invalid output from credential_process `{}`: {}",
+            command, err
+        )
+        .into()
+    })
+}
+
+fn parse_process_output(stdout: &[u8]) -> Result<ProcessCredentials, Box<dyn Error>> {
+    let credentials: ProcessCredentials = serde_json::from_slice(stdout)?;
+    if let Some(version) = credentials.version.filter(|version| *version != 1) {
+        return Err(format!("unsupported Version {}", version).into());
+    }
+    if credentials.access_key_id.is_empty() || credentials.secret_access_key.is_empty() {
+        return Err("AccessKeyId and SecretAccessKey must not be empty".into());
+    }
+    Ok(credentials)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_process_output() {
+        let credentials = parse_process_output(
+            br#"{"Version":1,"AccessKeyId":"ak","SecretAccessKey":"sk","SessionToken":"token","Expiration":"2030-01-01T00:00:00Z"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(credentials.access_key_id, "ak");
+        assert_eq!(credentials.session_token.as_deref(), Some("token"));
+        assert!(credentials.is_fresh());
+    }
+
+    #[test]
+    fn test_parse_invalid_process_output() {
+        assert!(parse_process_output(
+            br#"{"Version":2,"AccessKeyId":"ak","SecretAccessKey":"sk"}"#
+        )
+        .is_err());
+        assert!(parse_process_output(br#"{"AccessKeyId":"","SecretAccessKey":"sk"}"#).is_err());
+        assert!(parse_process_output(b"not json").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_credential_process() {
+        let command = r#"echo '{"Version":1,"AccessKeyId":"ak","SecretAccessKey":"sk"}'"#;
+        let credentials = resolve_credential_process(command).unwrap();
+
+        assert_eq!(credentials.secret_access_key, "sk");
+        assert!(resolve_credential_process("exit 3").is_err());
+    }
+}
@@ -0,0 +1,152 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use reqwest::header::HeaderMap;
+use reqwest::{Client, Request, Response};
+use std::time::Instant;
+use tracing::Level;
+use url::Url;
+
+use crate::cmd::tofu;
+
+const REDACTED: &str = "**REDACTED**";
+
+// 值整体隐藏的头部，Authorization 只隐藏 access key 和签名
+const SENSITIVE_HEADERS: &[&str] = &[
+    "proxy-authorization",
+    "x-amz-security-token",
+    "x-amz-server-side-encryption-customer-key",
+    "x-amz-copy-source-server-side-encryption-customer-key",
+    "cookie",
+    "set-cookie",
+];
+
+// 预签名 URL 中的签名参数
+const SENSITIVE_QUERY_PARAMS: &[&str] = &[
+    "X-Amz-Credential",
+    "X-Amz-Signature",
+    "X-Amz-Security-Token",
+];
+
+lazy_static! {
+    static ref SIGV4_CREDENTIAL: Regex = Regex::new(r"Credential=[^/,\s]+/").unwrap();
+    static ref SIGV4_SIGNATURE: Regex = Regex::new(r"Signature=[0-9a-fA-F]+").unwrap();
+}
+
+// 发送请求，--debug 时记录请求和响应的方法、URL、头部、状态码以及耗时
+pub async fn execute(client: &Client, request: Request) -> reqwest::Result<Response> {
+    if !tracing::enabled!(Level::DEBUG) {
+        return client.execute(request).await;
+    }
+
+    let url = redact_url(request.url());
+    tracing::debug!(
+        "--> {} {}\n{}",
+        request.method(),
+        url,
+        format_headers(request.headers())
+    );
+    let start = Instant::now();
+    let result = client.execute(request).await;
+    match &result {
+        Ok(response) => tracing::debug!(
+            "<-- {} {} ({:.1?})\n{}",
+            response.status(),
+            url,
+            start.elapsed(),
+            format_headers(response.headers())
+        ),
+        Err(err) => tracing::debug!(
+            "<-- {} failed after {:.1?}: {}",
+            url,
+            start.elapsed(),
+            tofu::error_chain(err)
+        ),
+    }
+    result
+}
+
+fn format_headers(headers: &HeaderMap) -> String {
+    let lines: Vec<String> = headers
+        .iter()
+        .map(|(name, value)| {
+            let value = String::from_utf8_lossy(value.as_bytes());
+            format!("    {}: {}", name, redact_header(name.as_str(), &value))
+        })
+        .collect();
+    lines.join("\n")
+}
+
+// 保留 SigV4 的签名范围和 SignedHeaders，便于排查 SignatureDoesNotMatch
+fn redact_header(name: &str, value: &str) -> String {
+    if SENSITIVE_HEADERS.contains(&name) {
+        return REDACTED.to_string();
+    }
+    if name != "authorization" {
+        return value.to_string();
+    }
+    if !value.starts_with("AWS4-HMAC-SHA256") {
+        return REDACTED.to_string();
+    }
+    let value = SIGV4_CREDENTIAL.replace(value, format!("Credential={}/", REDACTED));
+    SIGV4_SIGNATURE
+        .replace(&value, format!("Signature={}", REDACTED))
+        .into_owned()
+}
+
+fn redact_url(url: &Url) -> String {
+    let is_sensitive = |key: &str| {
+        SENSITIVE_QUERY_PARAMS
+            .iter()
+            .any(|param| param.eq_ignore_ascii_case(key))
+    };
+    if !url.query_pairs().any(|(key, _)| is_sensitive(&key)) {
+        return url.to_string();
+    }
+
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(key, value)| {
+            let value = if is_sensitive(&key) {
+                REDACTED.into()
+            } else {
+                value
+            };
+            (key.into_owned(), value.into_owned())
+        })
+        .collect();
+    let mut url = url.clone();
+    url.query_pairs_mut().clear().extend_pairs(pairs);
+    url.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact() {
+        let auth = "AWS4-HMAC-SHA256 Credential=AKIAEXAMPLE/20241018/us-east-1/s3/aws4_request, SignedHeaders=host;x-amz-date, Signature=0123abcdef";
+        assert_eq!(
+            redact_header("authorization", auth),
+            "AWS4-HMAC-SHA256 Credential=**REDACTED**/20241018/us-east-1/s3/aws4_request, SignedHeaders=host;x-amz-date, Signature=**REDACTED**"
+        );
+        assert_eq!(
+            redact_header("authorization", "Basic dXNlcjpwYXNz"),
+            REDACTED
+        );
+        assert_eq!(redact_header("x-amz-security-token", "token"), REDACTED);
+        assert_eq!(
+            redact_header("x-amz-date", "20241018T000000Z"),
+            "20241018T000000Z"
+        );
+
+        let url = Url::parse(
+            "https://s3.local/b/o?X-Amz-Credential=AK%2F2024&X-Amz-Signature=abc&versionId=1",
+        )
+        .unwrap();
+        assert_eq!(
+            redact_url(&url),
+            "https://s3.local/b/o?X-Amz-Credential=**REDACTED**&X-Amz-Signature=**REDACTED**&versionId=1"
+        );
+    }
+}
@@ -1 +1,4 @@
 pub mod client;
+pub mod credential;
+pub mod http;
+pub mod httptrace;